@@ -1,5 +1,5 @@
 use macroquad::prelude::*;
-use resphys::{Collider, ColliderState, AABB, FP};
+use resphys::{DebugColor, DebugDraw, DebugDrawStyle, AABB, FP};
 
 // Body creation with builder assistance, event iteration and deletion of bodies
 
@@ -118,7 +118,7 @@ async fn main() {
             };
             let player_body_handle = colliders[player].owner;
             let player_body = &mut bodies[player_body_handle];
-            player_body.velocity = player_body.velocity * vel_mask;
+            player_body.velocity *= vel_mask;
 
             to_remove.into_iter().for_each(|collision_handle| {
                 let collider_owner = colliders[collision_handle].owner;
@@ -130,44 +130,67 @@ async fn main() {
         }
 
         clear_background(Color::new(0., 1., 1., 1.));
-        for (_, collider) in colliders.iter() {
-            let body = &bodies[collider.owner];
-            draw_collider(&collider, body.position);
-        }
+        resphys::debug_draw(
+            &mut MacroquadDraw,
+            &DebugDrawStyle::default(),
+            &physics,
+            &bodies,
+            &colliders,
+            &[],
+        );
 
         next_frame().await
     }
 }
 
-fn draw_collider(collider: &Collider<TagType>, position: Vec2) {
-    let mut color = match collider.state {
-        ColliderState::Solid => BLUE,
-        ColliderState::Sensor => YELLOW,
-    };
-    // Quickly change color's alpha
-    let fill_color = color;
-
-    color.a = 0.3;
-    // This works because there's currently only AABB shape. Half extents.
-    let wh = collider.shape.half_exts;
-    let x_pos = FP::to_num::<f32>(position.x() - wh.x() + collider.offset.x());
-    let y_pos = FP::to_num::<f32>(position.y() - wh.y() + collider.offset.y());
-    draw_rectangle(
-        x_pos,
-        y_pos,
-        FP::to_num::<f32>(wh.x()) * 2.,
-        FP::to_num::<f32>(wh.y()) * 2.,
-        color,
-    );
-    draw_rectangle_lines(
-        x_pos,
-        y_pos,
-        FP::to_num::<f32>(wh.x()) * 2.,
-        FP::to_num::<f32>(wh.y()) * 2.,
-        3.,
-        fill_color,
-    );
+// Debug draw backend, any renderer can be plugged in the same way
+struct MacroquadDraw;
+
+fn to_color(color: DebugColor) -> Color {
+    Color::new(color.r, color.g, color.b, color.a)
+}
+
+impl DebugDraw for MacroquadDraw {
+    fn draw_rect(&mut self, position: Vec2, half_exts: Vec2, color: DebugColor) {
+        let outline = to_color(color);
+        let mut fill = outline;
+        fill.a = 0.3;
+        let x_pos = FP::to_num::<f32>(position.x() - half_exts.x());
+        let y_pos = FP::to_num::<f32>(position.y() - half_exts.y());
+        let width = FP::to_num::<f32>(half_exts.x()) * 2.;
+        let height = FP::to_num::<f32>(half_exts.y()) * 2.;
+        draw_rectangle(x_pos, y_pos, width, height, fill);
+        draw_rectangle_lines(x_pos, y_pos, width, height, 3., outline);
+    }
+    fn draw_line(&mut self, from: Vec2, to: Vec2, color: DebugColor) {
+        draw_line(
+            FP::to_num::<f32>(from.x()),
+            FP::to_num::<f32>(from.y()),
+            FP::to_num::<f32>(to.x()),
+            FP::to_num::<f32>(to.y()),
+            2.,
+            to_color(color),
+        );
+    }
+    fn draw_point(&mut self, position: Vec2, color: DebugColor) {
+        draw_circle(
+            FP::to_num::<f32>(position.x()),
+            FP::to_num::<f32>(position.y()),
+            3.,
+            to_color(color),
+        );
+    }
+    fn draw_text(&mut self, position: Vec2, text: &str, color: DebugColor) {
+        draw_text(
+            text,
+            FP::to_num::<f32>(position.x()),
+            FP::to_num::<f32>(position.y()),
+            16.,
+            to_color(color),
+        );
+    }
 }
+
 #[derive(Clone, Copy, Debug)]
 enum TagType {
     Moving,
@@ -51,38 +51,17 @@ async fn main() {
 
     // world generation
 
-    for x in (0..=768).step_by(32) {
-        add_tile(
-            &mut physics,
-            &mut bodies,
-            &mut colliders,
-            Vec2::from(16. + x as f32, 16.),
-        );
-    }
-    for y in (32..=544).step_by(32) {
-        add_tile(
-            &mut physics,
-            &mut bodies,
-            &mut colliders,
-            Vec2::from(16., 16. + y as f32),
-        );
-    }
-    for y in (32..=544).step_by(32) {
-        add_tile(
-            &mut physics,
-            &mut bodies,
-            &mut colliders,
-            Vec2::from(768. + 16., 16. + y as f32),
-        );
-    }
-    for x in (32..=768 - 32).step_by(32) {
-        add_tile(
-            &mut physics,
-            &mut bodies,
-            &mut colliders,
-            Vec2::from(16. + x as f32, 544. + 16.),
-        );
-    }
+    // 25x18 grid of 32x32 tiles with solid borders, merged into 4 colliders
+    let (width, height) = (25, 18);
+    let cells = (0..width * height)
+        .map(|index| {
+            let (x, y) = (index % width, index / width);
+            x == 0 || y == 0 || x == width - 1 || y == height - 1
+        })
+        .collect();
+    let _tilemap = resphys::TilemapDesc::new(width, height, Vec2::from(32., 32.), TagType::Tile)
+        .with_cells(cells)
+        .build(&mut physics, &mut bodies, &mut colliders);
 
     let mut remaining_time = 0.;
     loop {
@@ -98,24 +77,14 @@ async fn main() {
     }
 }
 
-fn check_grounded(physics: &mut PhysicsWorld, player: &mut Player) -> bool{
-    // check if there are no collisions
-    if physics
-        .collisions_of(player.collider_handle)
-        .peekable()
-        .peek()
-        .is_none()
-    {
-        return false;
-    } else {
-        for (_, info) in physics.collisions_of(player.collider_handle) {
-            //println!("info: {:?}", info);
-            if info.normal.y() > 0 {
-                return true;
-            }
+fn check_grounded(physics: &mut PhysicsWorld, player: &mut Player) -> bool {
+    for (_, info) in physics.collisions_of(player.collider_handle) {
+        //println!("info: {:?}", info);
+        if info.normal.y() > 0 {
+            return true;
         }
-        return false;
     }
+    false
 }
 
 fn physics_update(
@@ -136,12 +105,12 @@ fn physics_update(
 
     // gravity only happens when not grounded
     if !player.is_grounded {
-        player_body.velocity = player_body.velocity + gravity;
+        player_body.velocity += gravity;
     } else {
         player_body.velocity.set_y(0);
     }
 
-    player_body.velocity = controls(player_body.velocity, player);
+    player_body.velocity = controls(player_body.velocity);
 
     //println!("{}", player_body.velocity);
     //println!("{}", player.is_grounded);
@@ -155,12 +124,12 @@ fn render(bodies: &resphys::BodySet, colliders: &resphys::ColliderSet<TagType>)
     clear_background(Color::new(0., 1., 1., 1.));
     for (_, collider) in colliders.iter() {
         let body = &bodies[collider.owner];
-        draw_collider(&collider, body.position);
+        draw_collider(collider, body.position);
     }
 }
 
 // 32 is tile per second
-fn controls(mut velocity: Vec2, player: &Player) -> Vec2 {
+fn controls(mut velocity: Vec2) -> Vec2 {
     let input: f32 = {
         if is_key_down(KeyCode::Left) {
             -1.
@@ -171,7 +140,7 @@ fn controls(mut velocity: Vec2, player: &Player) -> Vec2 {
         }
     };
 
-    velocity = velocity + Vec2::from(input, 0.);
+    velocity += Vec2::from(input, 0.);
 
     // if movement pressed
 
@@ -187,26 +156,6 @@ fn controls(mut velocity: Vec2, player: &Player) -> Vec2 {
     velocity
 }
 
-fn add_tile(
-    physics: &mut PhysicsWorld,
-    bodies: &mut resphys::BodySet,
-    colliders: &mut resphys::ColliderSet<TagType>,
-    position: Vec2,
-) {
-    let body3 = resphys::builder::BodyDesc::new()
-        .with_position(position)
-        .make_static()
-        .build();
-    let collider3 = resphys::builder::ColliderDesc::new(
-        AABB {
-            half_exts: Vec2::from(16., 16.),
-        },
-        TagType::Tile,
-    );
-    let body3_handle = bodies.insert(body3);
-    colliders.insert(collider3.build(body3_handle), bodies, physics);
-}
-
 fn draw_collider(collider: &Collider<TagType>, position: Vec2) {
     let mut color = match collider.state {
         ColliderState::Solid => BLUE,
@@ -216,8 +165,8 @@ fn draw_collider(collider: &Collider<TagType>, position: Vec2) {
     let fill_color = color;
 
     color.a = 0.3;
-    // This works because there's currently only AABB shape. Half extents.
-    let wh = collider.shape.half_exts;
+    // Bounding box half extents, exact for AABB shapes.
+    let wh = collider.shape.half_exts();
     let x_pos = FP::to_num::<f32>(position.x() - wh.x() + collider.offset.x());
     let y_pos = FP::to_num::<f32>(position.y() - wh.y() + collider.offset.y());
     draw_rectangle(
@@ -66,7 +66,7 @@ async fn main() {
         clear_background(Color::new(0., 1., 1., 1.));
         for (_, collider) in colliders.iter() {
             let body = &bodies[collider.owner];
-            draw_collider(&collider, body.position);
+            draw_collider(collider, body.position);
         }
 
         next_frame().await
@@ -82,8 +82,8 @@ fn draw_collider(collider: &Collider<TagType>, position: Vec2) {
     let fill_color = color;
 
     color.a = 0.3;
-    // This works because there's currently only AABB shape. Half extents.
-    let wh = collider.shape.half_exts;
+    // Bounding box half extents, exact for AABB shapes.
+    let wh = collider.shape.half_exts();
     let x_pos = FP::to_num::<f32>(position.x() - wh.x() + collider.offset.x());
     let y_pos = FP::to_num::<f32>(position.y() - wh.y() + collider.offset.y());
     draw_rectangle(
@@ -71,7 +71,7 @@ async fn main() {
         while remaining_time >= FPS_INV {
             let player_body = &mut bodies[player_bhandle];
 
-            player_body.velocity = player_body.velocity + Vec2::from(0., 64. * FPS_INV);
+            player_body.velocity += Vec2::from(0., 64. * FPS_INV);
 
             player_body.velocity = controls(player_body.velocity);
 
@@ -82,7 +82,7 @@ async fn main() {
         clear_background(Color::new(0., 1., 1., 1.));
         for (_, collider) in colliders.iter() {
             let body = &bodies[collider.owner];
-            draw_collider(&collider, body.position);
+            draw_collider(collider, body.position);
         }
 
         next_frame().await
@@ -101,7 +101,7 @@ fn controls(mut velocity: Vec2) -> Vec2 {
         }
     };
 
-    velocity = velocity + Vec2::from(input * 32. * 8. * FPS_INV, 0.);
+    velocity += Vec2::from(input * 32. * 8. * FPS_INV, 0.);
 
     // if movement pressed
 
@@ -115,7 +115,7 @@ fn controls(mut velocity: Vec2) -> Vec2 {
         .min(FP::from_num(32. * 4.));
 
     if is_key_pressed(KeyCode::Up) {
-        velocity = velocity + Vec2::from(0., -128.);
+        velocity += Vec2::from(0., -128.);
     }
     velocity
 }
@@ -149,8 +149,8 @@ fn draw_collider(collider: &Collider<TagType>, position: Vec2) {
     let fill_color = color;
 
     color.a = 0.3;
-    // This works because there's currently only AABB shape. Half extents.
-    let wh = collider.shape.half_exts;
+    // Bounding box half extents, exact for AABB shapes.
+    let wh = collider.shape.half_exts();
     let x_pos = FP::to_num::<f32>(position.x() - wh.x() + collider.offset.x());
     let y_pos = FP::to_num::<f32>(position.y() - wh.y() + collider.offset.y());
     draw_rectangle(
@@ -76,7 +76,7 @@ async fn main() {
         while remaining_time >= FPS_INV {
             let player_body = &mut bodies[player_bhandle];
 
-            player_body.velocity = player_body.velocity + Vec2::from(0., 64. * FPS_INV);
+            player_body.velocity += Vec2::from(0., 64. * FPS_INV);
 
             player_body.velocity = controls(player_body.velocity);
 
@@ -87,7 +87,7 @@ async fn main() {
         clear_background(Color::new(0., 1., 1., 1.));
         for (_, collider) in colliders.iter() {
             let body = &bodies[collider.owner];
-            draw_collider(&collider, body.position);
+            draw_collider(collider, body.position);
         }
 
         let overlap_pos = Vec2::from(150., 150.);
@@ -151,7 +151,7 @@ fn controls(mut velocity: Vec2) -> Vec2 {
         }
     };
 
-    velocity = velocity + Vec2::from(input * 32. * 8. * FPS_INV, 0.);
+    velocity += Vec2::from(input * 32. * 8. * FPS_INV, 0.);
 
     // if movement pressed
 
@@ -165,7 +165,7 @@ fn controls(mut velocity: Vec2) -> Vec2 {
         .min(FP::from_num(32. * 4.));
 
     if is_key_pressed(KeyCode::Up) {
-        velocity = velocity + Vec2::from(0., -128.);
+        velocity += Vec2::from(0., -128.);
     }
     velocity
 }
@@ -201,7 +201,7 @@ fn draw_collider(collider: &Collider<TagType>, position: Vec2) {
 
     color.a = 0.3;
 
-    let wh = collider.shape.half_exts;
+    let wh = collider.shape.half_exts();
     let x_pos = FP::to_num::<f32>(position.x() - wh.x() + collider.offset.x());
     let y_pos = FP::to_num::<f32>(position.y() - wh.y() + collider.offset.y());
     draw_rectangle(
@@ -231,7 +231,7 @@ fn draw_overlap_test_collider(collider: &Collider<TagType>, position: Vec2) {
 
     color.a = 0.3;
 
-    let wh = collider.shape.half_exts;
+    let wh = collider.shape.half_exts();
     let x_pos = FP::to_num::<f32>(position.x() - wh.x() + collider.offset.x());
     let y_pos = FP::to_num::<f32>(position.y() - wh.y() + collider.offset.y());
     draw_rectangle(
@@ -1,21 +1,21 @@
 // use glam::Vec2;
-use crate::{to_fp, Vec2, FP};
+use crate::{Scalar, Vec2, FP};
 //use fixed_macro::fixed;
 
 #[derive(Default, Copy, Clone, Debug)]
-pub struct AABB {
+pub struct AABB<N = FP> {
     /// half width, half height
-    pub half_exts: Vec2,
+    pub half_exts: Vec2<N>,
 }
 
 /// Minimal structure containing only the most important information about the collision.
 #[derive(Debug, Clone)]
-pub struct CollisionInfo {
-    pub normal: Vec2,
+pub struct CollisionInfo<N = FP> {
+    pub normal: Vec2<N>,
 }
 
-impl From<&Contact> for CollisionInfo {
-    fn from(contact: &Contact) -> Self {
+impl<N: Scalar> From<&Contact<N>> for CollisionInfo<N> {
+    fn from(contact: &Contact<N>) -> Self {
         Self {
             normal: contact.normal,
         }
@@ -23,14 +23,14 @@ impl From<&Contact> for CollisionInfo {
 }
 
 #[derive(Debug, Clone)]
-pub struct Contact {
-    pub depth: FP,
-    pub normal: Vec2,
-    pub contact_point: Vec2,
+pub struct Contact<N = FP> {
+    pub depth: N,
+    pub normal: Vec2<N>,
+    pub contact_point: Vec2<N>,
 }
 
-impl Contact {
-    pub fn new(depth: FP, normal: Vec2, contact_point: Vec2) -> Self {
+impl<N: Scalar> Contact<N> {
+    pub fn new(depth: N, normal: Vec2<N>, contact_point: Vec2<N>) -> Self {
         Self {
             depth,
             normal,
@@ -40,13 +40,13 @@ impl Contact {
 }
 
 #[derive(Debug)]
-pub struct ContactManifold {
-    pub contact_x: Contact,
-    pub contact_y: Contact,
+pub struct ContactManifold<N = FP> {
+    pub contact_x: Contact<N>,
+    pub contact_y: Contact<N>,
 }
 
-impl ContactManifold {
-    pub fn best_contact(&self) -> &Contact {
+impl<N: Scalar> ContactManifold<N> {
+    pub fn best_contact(&self) -> &Contact<N> {
         if self.contact_x.depth < self.contact_y.depth {
             &self.contact_x
         } else {
@@ -56,11 +56,11 @@ impl ContactManifold {
 }
 
 // ported https://github.com/RandyGaul/cute_headers/blob/master/cute_c2.h#L1193
-pub fn intersection_aabb_aabb(
-    a_loc: Vec2,
-    a_half_exts: Vec2,
-    b_loc: Vec2,
-    b_half_exts: Vec2,
+pub fn intersection_aabb_aabb<N: Scalar>(
+    a_loc: Vec2<N>,
+    a_half_exts: Vec2<N>,
+    b_loc: Vec2<N>,
+    b_half_exts: Vec2<N>,
 ) -> bool {
     let a_min = a_loc - a_half_exts;
     let a_max = a_loc + a_half_exts;
@@ -76,29 +76,29 @@ pub fn intersection_aabb_aabb(
 }
 
 // contact points aren't precise - they are currently in the middle on the correct side
-pub fn contact_aabb_aabb(
-    a_loc: Vec2,
-    a_half_exts: Vec2,
-    b_loc: Vec2,
-    b_half_exts: Vec2,
-) -> Option<ContactManifold> {
+pub fn contact_aabb_aabb<N: Scalar>(
+    a_loc: Vec2<N>,
+    a_half_exts: Vec2<N>,
+    b_loc: Vec2<N>,
+    b_half_exts: Vec2<N>,
+) -> Option<ContactManifold<N>> {
     let distance = b_loc - a_loc;
 
     let overlap = a_half_exts + b_half_exts - distance.abs();
 
-    if overlap.x() < 0. || overlap.y() < 0. {
+    if overlap.x() < N::zero() || overlap.y() < N::zero() {
         return None;
     }
 
     let depth1 = overlap.x();
     //let normal1 = Vec2::new(distance.x().signum(), 0.);
-    let normal1 = Vec2::new(distance.x().signum(), to_fp(0.));
+    let normal1 = Vec2::new(distance.x().signum(), N::zero());
     let contact_point_x = Vec2::new(a_loc.x() + a_half_exts.x() * normal1.x(), a_loc.y());
     let contact1 = Contact::new(depth1, normal1, contact_point_x);
 
     let depth2 = overlap.y();
     //let normal2 = Vec2::new(0., distance.y().signum());
-    let normal2 = Vec2::new(to_fp(0.), distance.y().signum());
+    let normal2 = Vec2::new(N::zero(), distance.y().signum());
     let contact_point_y = Vec2::new(a_loc.x(), a_loc.y() + a_half_exts.y() * normal2.y());
     let contact2 = Contact::new(depth2, normal2, contact_point_y);
 
@@ -0,0 +1,389 @@
+use super::aabb::Contact;
+use super::ray::{contact_ray_aabb, Ray, Raycast};
+use super::sat::contact_convex;
+use crate::{Scalar, Vec2, FP};
+
+/// Vertical capsule, a segment of `2 * half_height` centered at the collider's position
+/// with both ends rounded by `radius`.
+#[derive(Default, Copy, Clone, Debug)]
+pub struct Capsule<N = FP> {
+    pub half_height: N,
+    pub radius: N,
+}
+
+impl<N: Scalar> Capsule<N> {
+    pub fn new(half_height: N, radius: N) -> Self {
+        Self {
+            half_height,
+            radius,
+        }
+    }
+    /// Top and bottom point of the inner segment.
+    pub fn segment(&self, position: Vec2<N>) -> (Vec2<N>, Vec2<N>) {
+        let half = Vec2::new(N::zero(), self.half_height);
+        (position - half, position + half)
+    }
+    pub fn bounding_half_exts(&self) -> Vec2<N> {
+        Vec2::new(self.radius, self.half_height + self.radius)
+    }
+}
+
+pub fn intersection_capsule_capsule<N: Scalar>(
+    a_loc: Vec2<N>,
+    a: &Capsule<N>,
+    b_loc: Vec2<N>,
+    b: &Capsule<N>,
+) -> bool {
+    contact_capsule_capsule(a_loc, a, b_loc, b).is_some()
+}
+
+/// The normal points from `a` towards `b`.
+pub fn contact_capsule_capsule<N: Scalar>(
+    a_loc: Vec2<N>,
+    a: &Capsule<N>,
+    b_loc: Vec2<N>,
+    b: &Capsule<N>,
+) -> Option<Contact<N>> {
+    let distance = b_loc - a_loc;
+    // both segments are vertical, so the closest points differ by the gap between them on each axis
+    let gap_y = (distance.y().abs() - a.half_height - b.half_height).partial_max(N::zero());
+    let between = Vec2::new(distance.x(), gap_y * distance.y().signum());
+    let length = between.length();
+    let radii = a.radius + b.radius;
+    if length > radii {
+        return None;
+    }
+    let normal = if length > N::zero() {
+        between / length
+    } else if distance.y() != N::zero() {
+        Vec2::new(N::zero(), distance.y().signum())
+    } else {
+        Vec2::unit_x()
+    };
+    // point of the segment `a` closest to the segment `b`
+    let closest = Vec2::new(
+        a_loc.x(),
+        b_loc
+            .y()
+            .partial_max(a_loc.y() - a.half_height)
+            .partial_min(a_loc.y() + a.half_height),
+    );
+    Some(Contact::new(
+        radii - length,
+        normal,
+        closest + normal * a.radius,
+    ))
+}
+
+// Capsule against a convex polygon given by its vertices in world space, the normal points towards the polygon.
+pub(crate) fn contact_capsule_convex<N: Scalar>(
+    capsule_loc: Vec2<N>,
+    capsule: &Capsule<N>,
+    polygon_loc: Vec2<N>,
+    polygon: &[Vec2<N>],
+) -> Option<Contact<N>> {
+    let (top, bottom) = capsule.segment(capsule_loc);
+    contact_segment_convex(
+        capsule_loc,
+        (top, bottom),
+        capsule.radius,
+        polygon_loc,
+        polygon,
+    )
+}
+
+// Segment of any orientation rounded by `radius` against a convex polygon
+pub(crate) fn contact_segment_convex<N: Scalar>(
+    center: Vec2<N>,
+    (top, bottom): (Vec2<N>, Vec2<N>),
+    radius: N,
+    polygon_loc: Vec2<N>,
+    polygon: &[Vec2<N>],
+) -> Option<Contact<N>> {
+    // the inner segment itself reaches into the polygon
+    if let Some(contact) = contact_convex(center, &[top, bottom], polygon_loc, polygon) {
+        return Some(Contact::new(
+            contact.depth + radius,
+            contact.normal,
+            contact.contact_point,
+        ));
+    }
+
+    // otherwise the closest points are found at an endpoint of either the segment or one of the edges
+    let mut closest: Option<(N, Vec2<N>, Vec2<N>)> = None;
+    let mut consider = |on_capsule: Vec2<N>, on_polygon: Vec2<N>| {
+        let distance = (on_polygon - on_capsule).length_squared();
+        if closest.is_none_or(|(best, _, _)| distance < best) {
+            closest = Some((distance, on_capsule, on_polygon));
+        }
+    };
+    for (i, vertex) in polygon.iter().enumerate() {
+        let next = polygon[(i + 1) % polygon.len()];
+        consider(top, closest_on_segment(top, *vertex, next));
+        consider(bottom, closest_on_segment(bottom, *vertex, next));
+        consider(closest_on_segment(*vertex, top, bottom), *vertex);
+    }
+    let (distance_squared, on_capsule, on_polygon) = closest?;
+    if distance_squared > radius * radius {
+        return None;
+    }
+    let distance = distance_squared.sqrt();
+    let normal = if distance > N::zero() {
+        (on_polygon - on_capsule) / distance
+    } else {
+        (polygon_loc - center).normalized()
+    };
+    Some(Contact::new(radius - distance, normal, on_polygon))
+}
+
+/// Casts against the inner rectangle and both rounded ends, the earliest hit wins.
+pub fn contact_ray_capsule<N: Scalar>(
+    ray: &Ray<N>,
+    capsule_pos: Vec2<N>,
+    capsule: &Capsule<N>,
+) -> Option<Raycast<N>> {
+    let (top, bottom) = capsule.segment(capsule_pos);
+    let hits = [
+        contact_ray_aabb(
+            ray,
+            capsule_pos,
+            Vec2::new(capsule.radius, capsule.half_height),
+        ),
+        contact_ray_circle(ray, top, capsule.radius),
+        contact_ray_circle(ray, bottom, capsule.radius),
+    ];
+    hits.iter()
+        .flatten()
+        .fold(None, |earliest: Option<&Raycast<N>>, hit| match earliest {
+            Some(earliest) if earliest.toi <= hit.toi => Some(earliest),
+            _ => Some(hit),
+        })
+        .cloned()
+}
+
+pub(crate) fn contact_ray_circle<N: Scalar>(
+    ray: &Ray<N>,
+    center: Vec2<N>,
+    radius: N,
+) -> Option<Raycast<N>> {
+    let from_center = ray.origin - center;
+    let c = from_center.length_squared() - radius * radius;
+    if c <= N::zero() {
+        // started inside
+        let normal = if from_center == Vec2::zero() {
+            -ray.dir.normalized()
+        } else {
+            from_center.normalized()
+        };
+        return Some(Raycast {
+            toi: N::zero(),
+            normal,
+        });
+    }
+    let a = ray.dir.length_squared();
+    let b = ray.dir.dot(from_center);
+    if a == N::zero() || b >= N::zero() {
+        return None;
+    }
+    let discriminant = b * b - a * c;
+    if discriminant < N::zero() {
+        return None;
+    }
+    let toi = (-b - discriminant.sqrt()) / a;
+    if toi > ray.toi {
+        return None;
+    }
+    Some(Raycast {
+        toi,
+        normal: (ray.origin + ray.dir * toi - center).normalized(),
+    })
+}
+
+pub(crate) fn closest_on_segment<N: Scalar>(point: Vec2<N>, a: Vec2<N>, b: Vec2<N>) -> Vec2<N> {
+    let ab = b - a;
+    let length_squared = ab.length_squared();
+    if length_squared == N::zero() {
+        return a;
+    }
+    let t = ((point - a).dot(ab) / length_squared)
+        .partial_max(N::zero())
+        .partial_min(N::one());
+    a + ab * t
+}
+
+// Sum of the extents of a capsule and a box along each axis, at the current distance on the other axis.
+// Used by the axis separated solver, the rounded ends let the capsule slide past corners.
+pub(crate) fn reach_capsule_aabb<N: Scalar>(
+    capsule_loc: Vec2<N>,
+    capsule: &Capsule<N>,
+    aabb_loc: Vec2<N>,
+    aabb_half_exts: Vec2<N>,
+) -> Vec2<N> {
+    let distance = (aabb_loc - capsule_loc).abs();
+    let gap =
+        (distance - aabb_half_exts - Vec2::new(N::zero(), capsule.half_height)).max(Vec2::zero());
+    Vec2::new(
+        aabb_half_exts.x() + circle_extent(capsule.radius, gap.y()),
+        aabb_half_exts.y() + capsule.half_height + circle_extent(capsule.radius, gap.x()),
+    )
+}
+
+pub(crate) fn reach_capsule_capsule<N: Scalar>(
+    a_loc: Vec2<N>,
+    a: &Capsule<N>,
+    b_loc: Vec2<N>,
+    b: &Capsule<N>,
+) -> Vec2<N> {
+    let distance = (b_loc - a_loc).abs();
+    let radii = a.radius + b.radius;
+    let gap_y = (distance.y() - a.half_height - b.half_height).partial_max(N::zero());
+    Vec2::new(
+        circle_extent(radii, gap_y),
+        a.half_height + b.half_height + circle_extent(radii, distance.x()),
+    )
+}
+
+// half of the chord of a circle, `offset` away from its center
+fn circle_extent<N: Scalar>(radius: N, offset: N) -> N {
+    (radius * radius - offset * offset)
+        .partial_max(N::zero())
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{BodyDesc, ColliderDesc};
+    use crate::{to_fp, BodySet, ColliderSet, PhysicsWorld, Shape, AABB};
+
+    fn character() -> Capsule {
+        Capsule::new(to_fp(8), to_fp(4))
+    }
+
+    #[test]
+    fn contacts_and_rays() {
+        let capsule = Shape::from(character());
+        let floor = Shape::from(AABB {
+            half_exts: Vec2::from(16, 4),
+        });
+        let contact = capsule
+            .contact(Vec2::zero(), &floor, Vec2::from(0, 15))
+            .unwrap();
+        assert_eq!((contact.depth, contact.normal), (to_fp(1), Vec2::unit_y()));
+        let contact = floor
+            .contact(Vec2::from(0, 15), &capsule, Vec2::zero())
+            .unwrap();
+        assert_eq!((contact.depth, contact.normal), (to_fp(1), -Vec2::unit_y()));
+        // the box corner is outside of the rounded end
+        assert!(!capsule.intersects(Vec2::zero(), &floor, Vec2::from(19, 15)));
+
+        let other = Shape::from(character());
+        let contact = capsule
+            .contact(Vec2::zero(), &other, Vec2::from(6, 10))
+            .unwrap();
+        assert_eq!((contact.depth, contact.normal), (to_fp(2), Vec2::unit_x()));
+        assert!(!capsule.intersects(Vec2::zero(), &other, Vec2::from(6, 22)));
+
+        let ray = Ray {
+            origin: Vec2::from(0, -30),
+            dir: Vec2::from(0, 30),
+            toi: to_fp(1),
+        };
+        let raycast = capsule.ray_contact(Vec2::zero(), &ray).unwrap();
+        let hit = ray.origin + ray.dir * raycast.toi;
+        assert!((hit - Vec2::from(0, -12)).length() < to_fp(0.001));
+        assert_eq!(raycast.normal, -Vec2::unit_y());
+        let side = Ray {
+            origin: Vec2::from(-30, 2),
+            dir: Vec2::from(30, 0),
+            toi: to_fp(1),
+        };
+        let raycast = capsule.ray_contact(Vec2::zero(), &side).unwrap();
+        let hit = side.origin + side.dir * raycast.toi;
+        assert!((hit - Vec2::from(-4, 2)).length() < to_fp(0.001));
+    }
+
+    #[test]
+    fn solver_resolves_against_boxes() {
+        let mut world = PhysicsWorld::new();
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+        // two floor tiles with a seam at x = 16 and a wall on the right
+        for (position, half_exts) in [
+            (Vec2::from(8, 40), Vec2::from(8, 8)),
+            (Vec2::from(24, 40), Vec2::from(8, 8)),
+            (Vec2::from(40, 0), Vec2::from(8, 24)),
+        ] {
+            let tile = bodies.insert(
+                BodyDesc::new()
+                    .with_position(position)
+                    .make_static()
+                    .build(),
+            );
+            colliders.insert(
+                ColliderDesc::new(AABB { half_exts }, ()).build(tile),
+                &mut bodies,
+                &mut world,
+            );
+        }
+        let body = bodies.insert(
+            BodyDesc::new()
+                .with_position(Vec2::from(4, 0))
+                .with_velocity(Vec2::from(120, 240))
+                .build(),
+        );
+        colliders.insert(
+            ColliderDesc::new(character(), ()).build(body),
+            &mut bodies,
+            &mut world,
+        );
+        for _ in 0..30 {
+            world.step(to_fp(1. / 60.), &mut bodies, &mut colliders);
+        }
+        // resting on the floor pressed against the wall
+        let position = bodies[body].position;
+        assert!((position - Vec2::from(28, 20)).length() < to_fp(0.01));
+    }
+
+    #[test]
+    fn rounded_end_rests_on_corners() {
+        let mut world = PhysicsWorld::new();
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+        let tile = bodies.insert(
+            BodyDesc::new()
+                .with_position(Vec2::from(0, 40))
+                .make_static()
+                .build(),
+        );
+        colliders.insert(
+            ColliderDesc::new(
+                AABB {
+                    half_exts: Vec2::from(8, 8),
+                },
+                (),
+            )
+            .build(tile),
+            &mut bodies,
+            &mut world,
+        );
+        // center 2 units past the edge, so the rounded end touches the corner lower than the flat top
+        let body = bodies.insert(
+            BodyDesc::new()
+                .with_position(Vec2::from(10, 0))
+                .with_velocity(Vec2::from(0, 240))
+                .build(),
+        );
+        colliders.insert(
+            ColliderDesc::new(character(), ()).build(body),
+            &mut bodies,
+            &mut world,
+        );
+        for _ in 0..30 {
+            world.step(to_fp(1. / 60.), &mut bodies, &mut colliders);
+        }
+        let expected = 32. - 8. - 12f64.sqrt();
+        let y = bodies[body].position.y().to_num::<f64>();
+        assert!((y - expected).abs() < 0.01, "{}", y);
+    }
+}
@@ -0,0 +1,158 @@
+use super::capsule::contact_ray_circle;
+use super::chain::cast_shape_chain;
+use super::polygon::{contact_ray_polygon, convex_hull, ConvexPolygon};
+use super::ray::{Ray, Raycast};
+use super::shape::Shape;
+use crate::{Scalar, Vec2};
+
+/// Earliest hit of `shape` at `position` moving by `motion` against `target`.
+/// `toi` is the fraction of `motion` travelled before touching and the normal is the one of `target`'s surface.
+/// Shapes overlapping at the start hit at 0, chains can't be moved.
+pub fn cast_shape<N: Scalar>(
+    shape: &Shape<N>,
+    position: Vec2<N>,
+    motion: Vec2<N>,
+    target: &Shape<N>,
+    target_position: Vec2<N>,
+) -> Option<Raycast<N>> {
+    if let Shape::Chain(chain) = target {
+        return cast_shape_chain(shape, position, motion, target_position, chain);
+    }
+    let (vertices, radius) = shape.rounded_parts(position)?;
+    let (target_vertices, target_radius) = target.rounded_parts(target_position)?;
+    cast_rounded(
+        position,
+        motion,
+        &vertices,
+        radius,
+        &target_vertices,
+        target_radius,
+    )
+}
+
+// Ray from `origin` against the Minkowski difference of two convex cores rounded by the sum of their radii.
+// The rounded part is cast as a circle at every corner and a rectangle along every edge of the core.
+pub(crate) fn cast_rounded<N: Scalar>(
+    origin: Vec2<N>,
+    motion: Vec2<N>,
+    vertices: &[Vec2<N>],
+    radius: N,
+    target_vertices: &[Vec2<N>],
+    target_radius: N,
+) -> Option<Raycast<N>> {
+    let points: Vec<_> = target_vertices
+        .iter()
+        .flat_map(|target| {
+            vertices
+                .iter()
+                .map(move |vertex| *target - *vertex + origin)
+        })
+        .collect();
+    let core = convex_hull(&points);
+    let radius = radius + target_radius;
+    let ray = Ray {
+        origin,
+        dir: motion,
+        toi: N::one(),
+    };
+
+    let mut hits = vec![];
+    if let Some(polygon) = ConvexPolygon::new(core.clone()) {
+        hits.push(contact_ray_polygon(&ray, Vec2::zero(), &polygon));
+    }
+    if radius > N::zero() {
+        for (i, corner) in core.iter().enumerate() {
+            hits.push(contact_ray_circle(&ray, *corner, radius));
+            let next = core[(i + 1) % core.len()];
+            if next == *corner {
+                continue;
+            }
+            let offset = -(next - *corner).perp().normalized() * radius;
+            if let Some(side) =
+                ConvexPolygon::new(vec![*corner, next, next + offset, *corner + offset])
+            {
+                hits.push(contact_ray_polygon(&ray, Vec2::zero(), &side));
+            }
+        }
+    }
+    hits.into_iter()
+        .flatten()
+        .fold(None, |earliest: Option<Raycast<N>>, hit| match earliest {
+            Some(earliest) if earliest.toi <= hit.toi => Some(earliest),
+            _ => Some(hit),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{to_fp, Capsule, AABB, OBB};
+
+    #[test]
+    fn casts_between_shapes() {
+        let square = Shape::from(AABB {
+            half_exts: Vec2::from(5, 5),
+        });
+        let hit = cast_shape(
+            &square,
+            Vec2::zero(),
+            Vec2::from(40, 0),
+            &square,
+            Vec2::from(30, 2),
+        )
+        .unwrap();
+        assert_eq!((hit.toi, hit.normal), (to_fp(0.5), -Vec2::unit_x()));
+        assert!(cast_shape(
+            &square,
+            Vec2::zero(),
+            Vec2::from(40, 0),
+            &square,
+            Vec2::from(30, 12)
+        )
+        .is_none());
+        // overlapping at the start
+        let hit = cast_shape(
+            &square,
+            Vec2::zero(),
+            Vec2::from(40, 0),
+            &square,
+            Vec2::from(8, 0),
+        );
+        assert_eq!(hit.unwrap().toi, to_fp(0));
+
+        // the rounded end of a capsule passing a box corner
+        let capsule = Shape::from(Capsule::new(to_fp(5), to_fp(3)));
+        let hit = cast_shape(
+            &capsule,
+            Vec2::from(-20, -12),
+            Vec2::from(40, 0),
+            &square,
+            Vec2::zero(),
+        )
+        .unwrap();
+        assert!(hit.normal.x() < to_fp(0) && hit.normal.y() < to_fp(0));
+        assert!(cast_shape(
+            &capsule,
+            Vec2::from(-20, -14),
+            Vec2::from(40, 0),
+            &square,
+            Vec2::zero()
+        )
+        .is_none());
+
+        let diamond = Shape::from(OBB::new(
+            Vec2::from(5, 5),
+            crate::math::frac_pi_2::<crate::FP>() / 2,
+        ));
+        let hit = cast_shape(
+            &square,
+            Vec2::from(-30, 0),
+            Vec2::from(40, 0),
+            &diamond,
+            Vec2::zero(),
+        )
+        .unwrap();
+        // the tip of the diamond at x = -7.07 touches the square's side
+        assert!((hit.toi - to_fp(0.4482)).abs() < to_fp(0.001));
+    }
+}
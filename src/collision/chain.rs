@@ -0,0 +1,420 @@
+use super::aabb::Contact;
+use super::capsule::contact_segment_convex;
+use super::cast::cast_rounded;
+use super::ray::{Ray, Raycast};
+use super::sat::{contact_convex, support};
+use super::shape::Shape;
+use crate::{Scalar, Vec2, FP};
+
+/// Open chain of line segments through points relative to the collider's position, meant for static terrain.
+/// Segments are one-sided, they only collide with shapes whose center is in front of them.
+/// The front of a segment is on its left when walking the chain on screen (y axis points down),
+/// so a segment going right faces up and an outline listed clockwise faces outwards.
+#[derive(Clone, Debug)]
+pub struct SegmentChain<N = FP> {
+    points: Vec<Vec2<N>>,
+}
+
+impl<N: Scalar> SegmentChain<N> {
+    /// Returns `None` for less than two points or if two consecutive points are equal.
+    pub fn new(points: Vec<Vec2<N>>) -> Option<Self> {
+        if points.len() < 2 || points.windows(2).any(|pair| pair[0] == pair[1]) {
+            return None;
+        }
+        Some(Self { points })
+    }
+    pub fn points(&self) -> &[Vec2<N>] {
+        &self.points
+    }
+    /// Start, end and front facing normal of every segment moved to `position`.
+    pub fn segments(
+        &self,
+        position: Vec2<N>,
+    ) -> impl Iterator<Item = (Vec2<N>, Vec2<N>, Vec2<N>)> + '_ {
+        self.points.windows(2).map(move |pair| {
+            let (start, end) = (position + pair[0], position + pair[1]);
+            (start, end, -(end - start).perp().normalized())
+        })
+    }
+    /// Half extents of the smallest `AABB` centered at the collider's position that contains the chain.
+    pub fn bounding_half_exts(&self) -> Vec2<N> {
+        self.points
+            .iter()
+            .fold(Vec2::zero(), |half_exts, point| half_exts.max(point.abs()))
+    }
+}
+
+/// Deepest contact with a segment facing the shape, the normal is the one of the segment.
+/// Chains never collide with other chains.
+pub fn contact_chain_shape<N: Scalar>(
+    chain_loc: Vec2<N>,
+    chain: &SegmentChain<N>,
+    shape_loc: Vec2<N>,
+    shape: &Shape<N>,
+) -> Option<Contact<N>> {
+    let (vertices, radius) = shape.rounded_parts(shape_loc)?;
+    let mut deepest: Option<Contact<N>> = None;
+    for (start, end, normal) in chain.segments(chain_loc) {
+        if normal.dot(shape_loc - start) <= N::zero() {
+            continue;
+        }
+        let middle = (start + end) / N::from_i32(2);
+        let touches = if vertices.len() == 2 {
+            contact_segment_convex(
+                shape_loc,
+                (vertices[0], vertices[1]),
+                radius,
+                middle,
+                &[start, end],
+            )
+            .is_some()
+        } else {
+            contact_convex(middle, &[start, end], shape_loc, &vertices).is_some()
+        };
+        if !touches {
+            continue;
+        }
+        // pushed out along the segment's normal only, so a shape can't get caught on a seam
+        let deepest_point = support(&vertices, -normal) - normal * radius;
+        let depth = normal.dot(start - deepest_point);
+        // strict comparison so the first segment wins ties
+        if deepest.as_ref().is_none_or(|best| depth > best.depth) {
+            deepest = Some(Contact::new(depth, normal, deepest_point));
+        }
+    }
+    deepest
+}
+
+/// Hits segments from the front only, rays starting behind a segment pass through it.
+pub fn contact_ray_chain<N: Scalar>(
+    ray: &Ray<N>,
+    chain_pos: Vec2<N>,
+    chain: &SegmentChain<N>,
+) -> Option<Raycast<N>> {
+    let mut earliest: Option<Raycast<N>> = None;
+    for (start, end, normal) in chain.segments(chain_pos) {
+        if normal.dot(ray.dir) >= N::zero() {
+            continue;
+        }
+        let edge = end - start;
+        let denominator = ray.dir.cross(edge);
+        let to_start = start - ray.origin;
+        let toi = to_start.cross(edge) / denominator;
+        let along = to_start.cross(ray.dir) / denominator;
+        if toi < N::zero() || toi > ray.toi || along < N::zero() || along > N::one() {
+            continue;
+        }
+        if earliest.as_ref().is_none_or(|best| toi < best.toi) {
+            earliest = Some(Raycast { toi, normal });
+        }
+    }
+    earliest
+}
+
+// Segments facing a shape centered at `position` which moves by `motion` towards them.
+fn facing_segments<'a, N: Scalar>(
+    chain_pos: Vec2<N>,
+    chain: &'a SegmentChain<N>,
+    position: Vec2<N>,
+    motion: Vec2<N>,
+) -> impl Iterator<Item = (Vec2<N>, Vec2<N>, Vec2<N>)> + 'a {
+    chain.segments(chain_pos).filter(move |(start, _, normal)| {
+        normal.dot(motion) < N::zero() && normal.dot(position - *start) > N::zero()
+    })
+}
+
+/// Earliest hit of `shape` moving by `motion` against a segment facing it, `toi` is the fraction of `motion`.
+pub fn cast_shape_chain<N: Scalar>(
+    shape: &Shape<N>,
+    position: Vec2<N>,
+    motion: Vec2<N>,
+    chain_pos: Vec2<N>,
+    chain: &SegmentChain<N>,
+) -> Option<Raycast<N>> {
+    let (vertices, radius) = shape.rounded_parts(position)?;
+    facing_segments(chain_pos, chain, position, motion)
+        .filter_map(|(start, end, _)| {
+            cast_rounded(
+                position,
+                motion,
+                &vertices,
+                radius,
+                &[start, end],
+                N::zero(),
+            )
+        })
+        .fold(None, |earliest: Option<Raycast<N>>, hit| match earliest {
+            Some(earliest) if earliest.toi <= hit.toi => Some(earliest),
+            _ => Some(hit),
+        })
+}
+
+// Signed distance `shape` can move along the unit `axis` before resting on the chain, `None` if nothing blocks it.
+// Blocking hits are found with the shape shrunk by `tolerance`, so resting on a seam between segments
+// doesn't catch on the next segment, then resolved with the exact shape so it ends up touching.
+pub(crate) fn chain_stop<N: Scalar>(
+    shape: &Shape<N>,
+    position: Vec2<N>,
+    axis: Vec2<N>,
+    distance: N,
+    chain_pos: Vec2<N>,
+    chain: &SegmentChain<N>,
+    tolerance: N,
+) -> Option<N> {
+    if distance == N::zero() {
+        return None;
+    }
+    let shrunk = shape.shrunk(tolerance)?;
+    let (shrunk_vertices, shrunk_radius) = shrunk.rounded_parts(position)?;
+    let (vertices, radius) = shape.rounded_parts(position)?;
+    let length = distance.abs();
+    let dir = axis * distance.signum();
+    let back_off = tolerance * N::from_i32(2);
+
+    let mut stop: Option<N> = None;
+    for (start, end, _) in facing_segments(chain_pos, chain, position, dir) {
+        let segment = [start, end];
+        let blocking = |hit: &Raycast<N>| hit.normal.dot(dir) < N::zero();
+        let Some(hit) = cast_rounded(
+            position,
+            dir * length,
+            &shrunk_vertices,
+            shrunk_radius,
+            &segment,
+            N::zero(),
+        )
+        .filter(blocking) else {
+            continue;
+        };
+        // cast from slightly behind, which also pushes back out of a shallow penetration
+        let exact = cast_rounded(
+            position - dir * back_off,
+            dir * (length + back_off),
+            &vertices,
+            radius,
+            &segment,
+            N::zero(),
+        )
+        .filter(blocking);
+        let allowed = match exact {
+            Some(exact) => exact.toi * (length + back_off) - back_off,
+            None => hit.toi * length,
+        };
+        if stop.is_none_or(|stop| allowed < stop) {
+            stop = Some(allowed);
+        }
+    }
+    stop.map(|allowed| allowed.partial_min(length) * distance.signum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{BodyDesc, ColliderDesc};
+    use crate::{to_fp, BodySet, Capsule, ColliderSet, PhysicsWorld, AABB};
+
+    // floor going right at y = 0 from x = -50 to 50 with a seam at 0, then a wall going up at x = 50
+    fn terrain() -> SegmentChain {
+        SegmentChain::new(vec![
+            Vec2::from(-50, 0),
+            Vec2::from(0, 0),
+            Vec2::from(50, 0),
+            Vec2::from(50, -50),
+        ])
+        .unwrap()
+    }
+
+    fn square() -> Shape {
+        AABB {
+            half_exts: Vec2::from(5, 5),
+        }
+        .into()
+    }
+
+    #[test]
+    fn one_sided_contacts_and_rays() {
+        assert!(SegmentChain::<FP>::new(vec![Vec2::zero()]).is_none());
+        assert!(SegmentChain::<FP>::new(vec![Vec2::zero(), Vec2::zero()]).is_none());
+
+        let terrain = Shape::from(terrain());
+        let contact = terrain
+            .contact(Vec2::zero(), &square(), Vec2::from(-20, -4))
+            .unwrap();
+        assert_eq!(contact.depth, to_fp(1));
+        assert_eq!(contact.normal, -Vec2::unit_y());
+        // the flipped contact points from the square towards the chain
+        let contact = square()
+            .contact(Vec2::from(-20, -4), &terrain, Vec2::zero())
+            .unwrap();
+        assert_eq!(contact.normal, Vec2::unit_y());
+        // centered behind the floor
+        assert!(!terrain.intersects(Vec2::zero(), &square(), Vec2::from(-20, 4)));
+        let capsule = Shape::from(Capsule::new(to_fp(5), to_fp(2)));
+        assert!(terrain.intersects(Vec2::zero(), &capsule, Vec2::from(-20, -6)));
+        assert!(!terrain.intersects(Vec2::zero(), &capsule, Vec2::from(-20, -8)));
+
+        let down = Ray {
+            origin: Vec2::from(-20, -10),
+            dir: Vec2::from(0, 20),
+            toi: to_fp(1),
+        };
+        let raycast = terrain.ray_contact(Vec2::zero(), &down).unwrap();
+        assert_eq!((raycast.toi, raycast.normal), (to_fp(0.5), -Vec2::unit_y()));
+        // from below the floor passes through
+        let up = Ray {
+            origin: Vec2::from(-20, 10),
+            dir: Vec2::from(0, -20),
+            toi: to_fp(1),
+        };
+        assert!(terrain.ray_contact(Vec2::zero(), &up).is_none());
+        let right = Ray {
+            origin: Vec2::from(20, -10),
+            dir: Vec2::from(60, 0),
+            toi: to_fp(1),
+        };
+        let raycast = terrain.ray_contact(Vec2::zero(), &right).unwrap();
+        assert_eq!((raycast.toi, raycast.normal), (to_fp(0.5), -Vec2::unit_x()));
+    }
+
+    #[test]
+    fn shape_casts() {
+        let terrain = terrain();
+        let hit = cast_shape_chain(
+            &square(),
+            Vec2::from(-20, -25),
+            Vec2::from(0, 40),
+            Vec2::zero(),
+            &terrain,
+        )
+        .unwrap();
+        assert_eq!((hit.toi, hit.normal), (to_fp(0.5), -Vec2::unit_y()));
+        // moving away or starting behind never hits
+        assert!(cast_shape_chain(
+            &square(),
+            Vec2::from(-20, -25),
+            Vec2::from(0, -40),
+            Vec2::zero(),
+            &terrain
+        )
+        .is_none());
+        assert!(cast_shape_chain(
+            &square(),
+            Vec2::from(-20, 25),
+            Vec2::from(0, -40),
+            Vec2::zero(),
+            &terrain
+        )
+        .is_none());
+        let capsule = Shape::from(Capsule::new(to_fp(5), to_fp(2)));
+        let hit = cast_shape_chain(
+            &capsule,
+            Vec2::from(20, -10),
+            Vec2::from(40, 0),
+            Vec2::zero(),
+            &terrain,
+        )
+        .unwrap();
+        assert!((hit.toi - to_fp(0.7)).abs() < to_fp(0.001));
+        assert_eq!(hit.normal, -Vec2::unit_x());
+    }
+
+    #[test]
+    fn kinematic_bodies_slide_over_seams_and_stop_at_walls() {
+        let mut world = PhysicsWorld::<()>::new();
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+        let ground = bodies.insert(BodyDesc::new().make_static().build());
+        colliders
+            .insert(
+                ColliderDesc::new(terrain(), ()).build(ground),
+                &mut bodies,
+                &mut world,
+            )
+            .unwrap();
+        let moving = bodies.insert(
+            BodyDesc::new()
+                .with_position(Vec2::from(-30, -20))
+                .with_velocity(Vec2::from(60, 600))
+                .build(),
+        );
+        colliders.insert(
+            ColliderDesc::new(square(), ()).build(moving),
+            &mut bodies,
+            &mut world,
+        );
+
+        for _ in 0..120 {
+            world.step(to_fp(1. / 60.), &mut bodies, &mut colliders);
+        }
+        // landed on the floor, slid over the seam at x = 0 and stopped at the wall
+        let position = bodies[moving].position;
+        assert!((position.y() - to_fp(-5)).abs() < to_fp(0.01));
+        assert!((position.x() - to_fp(45)).abs() < to_fp(0.01));
+
+        // chains only go on static bodies
+        let dynamic = bodies.insert(BodyDesc::new().build());
+        assert_eq!(
+            colliders.try_insert(
+                ColliderDesc::new(terrain(), ()).build(dynamic),
+                &mut bodies,
+                &mut world
+            ),
+            Err(crate::PhysicsError::ChainOnKinematicBody(dynamic))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "segment chain attached to kinematic body")]
+    fn insert_panics_on_chains_of_kinematic_bodies() {
+        let mut world = PhysicsWorld::<()>::new();
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+        let dynamic = bodies.insert(BodyDesc::new().build());
+        colliders.insert(
+            ColliderDesc::new(terrain(), ()).build(dynamic),
+            &mut bodies,
+            &mut world,
+        );
+    }
+
+    #[test]
+    fn jumps_through_from_below() {
+        let mut world = PhysicsWorld::<()>::new();
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+        let ground = bodies.insert(BodyDesc::new().make_static().build());
+        colliders.insert(
+            ColliderDesc::new(terrain(), ()).build(ground),
+            &mut bodies,
+            &mut world,
+        );
+        let moving = bodies.insert(
+            BodyDesc::new()
+                .with_position(Vec2::from(-20, 20))
+                .with_velocity(Vec2::from(0, -600))
+                .build(),
+        );
+        colliders.insert(
+            ColliderDesc::new(square(), ()).build(moving),
+            &mut bodies,
+            &mut world,
+        );
+        for _ in 0..6 {
+            world.step(to_fp(1. / 60.), &mut bodies, &mut colliders);
+        }
+        assert!((bodies[moving].position.y() - to_fp(-40)).abs() < to_fp(0.1));
+
+        let (hit, _) = world
+            .cast_shape(
+                &square(),
+                Vec2::from(-20, -40),
+                Vec2::from(0, 60),
+                u32::MAX,
+                &bodies,
+                &colliders,
+            )
+            .find(|(h, _)| colliders[*h].owner == ground)
+            .unwrap();
+        assert_eq!(colliders[hit].owner, ground);
+    }
+}
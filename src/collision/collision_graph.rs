@@ -1,5 +1,5 @@
 use crate::collision::aabb::CollisionInfo;
-use crate::ColliderHandle;
+use crate::{ColliderHandle, PhysicsError, Scalar, FP};
 use fxhash::FxHashMap;
 use petgraph::graph::{NodeIndex, UnGraph};
 use petgraph::visit::EdgeRef;
@@ -7,13 +7,13 @@ use petgraph::visit::EdgeRef;
 type HandleNodeMap = FxHashMap<ColliderHandle, NodeIndex<usize>>;
 
 #[derive(Debug, Clone)]
-pub enum Interaction {
-    Collision(CollisionInfo),
+pub enum Interaction<N = FP> {
+    Collision(CollisionInfo<N>),
     Overlap,
 }
 
-impl Interaction {
-    pub fn collision(&self) -> Option<&CollisionInfo> {
+impl<N: Scalar> Interaction<N> {
+    pub fn collision(&self) -> Option<&CollisionInfo<N>> {
         match self {
             Interaction::Collision(data) => Some(data),
             _ => None,
@@ -24,16 +24,25 @@ impl Interaction {
     }
 }
 
+/// Edge of the `CollisionGraph` between two colliders.
+#[derive(Debug, Clone)]
+pub struct InteractionEdge<N = FP> {
+    /// `None` for pairs the broadphase found that don't interact yet
+    pub interaction: Option<Interaction<N>>,
+    /// Step during which the interaction started, see `PhysicsWorld::steps`
+    pub started: u64,
+}
+
 /// Structure for storing informations about the active collisions.  
 /// Currently unaware of anything besides the handles that collide or whether the collision started this frame.  
 /// Stores result of broadphase that narrowphase should use.
-pub struct CollisionGraph {
+pub struct CollisionGraph<N = FP> {
     // <BodyHandle, whether it was added this update, index_type to match `bodies` struct>
-    pub src: UnGraph<ColliderHandle, Option<Interaction>, usize>,
+    pub src: UnGraph<ColliderHandle, InteractionEdge<N>, usize>,
     pub binding: HandleNodeMap,
 }
 
-impl CollisionGraph {
+impl<N: Scalar> CollisionGraph<N> {
     pub fn with_capacity(nodes: usize, edges: usize) -> Self {
         Self {
             src: UnGraph::with_capacity(nodes, edges),
@@ -46,39 +55,78 @@ impl CollisionGraph {
         self.binding.insert(handle, node_id);
     }
 
+    /// Panics if the collider isn't in the graph, see `try_get_node_index`.
     pub fn get_node_index(&self, handle: ColliderHandle) -> NodeIndex<usize> {
-        *self.binding.get(&handle).unwrap()
+        self.try_get_node_index(handle)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+    pub fn try_get_node_index(
+        &self,
+        handle: ColliderHandle,
+    ) -> Result<NodeIndex<usize>, PhysicsError> {
+        self.binding
+            .get(&handle)
+            .copied()
+            .ok_or(PhysicsError::ColliderNotInGraph(handle))
     }
 
+    /// Panics if either collider isn't in the graph, see `try_update_edge`.
     pub fn update_edge(&mut self, handle1: ColliderHandle, handle2: ColliderHandle) {
-        let node_id1 = &self.binding[&handle1];
-        let node_id2 = &self.binding[&handle2];
+        self.try_update_edge(handle1, handle2)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+    pub fn try_update_edge(
+        &mut self,
+        handle1: ColliderHandle,
+        handle2: ColliderHandle,
+    ) -> Result<(), PhysicsError> {
+        let node_id1 = self.try_get_node_index(handle1)?;
+        let node_id2 = self.try_get_node_index(handle2)?;
 
         // don't add the edge if it already exists
-        if !self.src.contains_edge(*node_id1, *node_id2) {
-            self.src.add_edge(*node_id1, *node_id2, None);
+        if !self.src.contains_edge(node_id1, node_id2) {
+            let edge = InteractionEdge {
+                interaction: None,
+                started: 0,
+            };
+            self.src.add_edge(node_id1, node_id2, edge);
         }
+        Ok(())
     }
 
+    /// Panics if the collider isn't in the graph, see `try_remove_node`.
     pub fn remove_node(&mut self, handle: ColliderHandle) {
-        let node_id = match self.binding.get(&handle) {
-            Some(&node_id) => node_id,
-            None => panic!("Trying to remove nonexistent node"),
-        };
+        self.try_remove_node(handle)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+    pub fn try_remove_node(&mut self, handle: ColliderHandle) -> Result<(), PhysicsError> {
+        let node_id = self
+            .binding
+            .remove(&handle)
+            .ok_or(PhysicsError::ColliderNotInGraph(handle))?;
         self.src.remove_node(node_id);
         // in case graph reallocated some other handle to this node
         if let Some(&new_handle) = self.src.node_weight(node_id) {
             self.binding.insert(new_handle, node_id);
         }
+        Ok(())
     }
 
+    /// Panics if the collider isn't in the graph, see `try_edges`.
     pub fn edges(
         &self,
         handle: ColliderHandle,
-    ) -> impl Iterator<Item = (crate::ColliderHandle, &Interaction)> {
-        let node_id = self.binding[&handle];
-        self.src
-            .edges(node_id)
-            .filter_map(move |edge| Some((self.src[edge.target()], edge.weight().as_ref()?)))
+    ) -> impl Iterator<Item = (crate::ColliderHandle, &Interaction<N>)> {
+        self.try_edges(handle)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+    pub fn try_edges(
+        &self,
+        handle: ColliderHandle,
+    ) -> Result<impl Iterator<Item = (crate::ColliderHandle, &Interaction<N>)>, PhysicsError> {
+        let node_id = self.try_get_node_index(handle)?;
+        Ok(self.src.edges(node_id).filter_map(move |edge| {
+            Some((self.src[edge.target()], edge.weight().interaction.as_ref()?))
+        }))
     }
 }
@@ -1,8 +1,24 @@
 mod aabb;
+mod capsule;
+mod cast;
+mod chain;
 mod collision_graph;
+mod obb;
+mod polygon;
 mod ray;
+mod sat;
+mod shape;
 
 pub use self::aabb::{contact_aabb_aabb, intersection_aabb_aabb, CollisionInfo};
-pub use self::aabb::{ContactManifold, AABB};
-pub use self::collision_graph::{CollisionGraph, Interaction};
+pub use self::aabb::{Contact, ContactManifold, AABB};
+pub use self::capsule::{
+    contact_capsule_capsule, contact_ray_capsule, intersection_capsule_capsule, Capsule,
+};
+pub use self::cast::cast_shape;
+pub(crate) use self::chain::chain_stop;
+pub use self::chain::{cast_shape_chain, contact_chain_shape, contact_ray_chain, SegmentChain};
+pub use self::collision_graph::{CollisionGraph, Interaction, InteractionEdge};
+pub use self::obb::{contact_obb_obb, contact_ray_obb, intersection_obb_obb, OBB};
+pub use self::polygon::{contact_polygon_polygon, contact_ray_polygon, ConvexPolygon};
 pub use self::ray::{contact_ray_aabb, Ray, Raycast};
+pub use self::shape::Shape;
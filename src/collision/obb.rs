@@ -0,0 +1,184 @@
+use super::aabb::{Contact, AABB};
+use super::ray::{contact_ray_aabb, Ray, Raycast};
+use super::sat::contact_convex;
+use crate::{Scalar, Vec2, FP};
+
+/// Oriented bounding box, a rectangle rotated around its center.
+#[derive(Default, Copy, Clone, Debug)]
+pub struct OBB<N = FP> {
+    /// half width, half height before the rotation
+    pub half_exts: Vec2<N>,
+    /// Rotation in radians, positive angles rotate clockwise on screen (y axis points down)
+    pub angle: N,
+}
+
+impl<N: Scalar> OBB<N> {
+    pub fn new(half_exts: Vec2<N>, angle: N) -> Self {
+        Self { half_exts, angle }
+    }
+    /// Local x and y axes of the box in world space.
+    pub fn axes(&self) -> (Vec2<N>, Vec2<N>) {
+        let axis_x = Vec2::from_angle(self.angle);
+        (axis_x, axis_x.perp())
+    }
+    /// Corners in clockwise order on screen, starting at the top left one before the rotation.
+    pub fn corners(&self, position: Vec2<N>) -> [Vec2<N>; 4] {
+        let (axis_x, axis_y) = self.axes();
+        let x = axis_x * self.half_exts.x();
+        let y = axis_y * self.half_exts.y();
+        [
+            position - x - y,
+            position + x - y,
+            position + x + y,
+            position - x + y,
+        ]
+    }
+    /// Half extents of the smallest `AABB` containing the box.
+    pub fn bounding_half_exts(&self) -> Vec2<N> {
+        let (axis_x, axis_y) = self.axes();
+        axis_x.abs() * self.half_exts.x() + axis_y.abs() * self.half_exts.y()
+    }
+}
+
+impl<N: Scalar> From<AABB<N>> for OBB<N> {
+    fn from(aabb: AABB<N>) -> Self {
+        Self::new(aabb.half_exts, N::zero())
+    }
+}
+
+pub fn intersection_obb_obb<N: Scalar>(
+    a_loc: Vec2<N>,
+    a: &OBB<N>,
+    b_loc: Vec2<N>,
+    b: &OBB<N>,
+) -> bool {
+    contact_obb_obb(a_loc, a, b_loc, b).is_some()
+}
+
+/// Separating axis test, the normal points from `a` towards `b`.
+pub fn contact_obb_obb<N: Scalar>(
+    a_loc: Vec2<N>,
+    a: &OBB<N>,
+    b_loc: Vec2<N>,
+    b: &OBB<N>,
+) -> Option<Contact<N>> {
+    contact_convex(a_loc, &a.corners(a_loc), b_loc, &b.corners(b_loc))
+}
+
+// the ray is moved into the local space of the box, where it's a regular `AABB`
+pub fn contact_ray_obb<N: Scalar>(
+    ray: &Ray<N>,
+    obb_pos: Vec2<N>,
+    obb: &OBB<N>,
+) -> Option<Raycast<N>> {
+    let local_ray = Ray {
+        origin: (ray.origin - obb_pos).rotated(-obb.angle),
+        dir: ray.dir.rotated(-obb.angle),
+        toi: ray.toi,
+    };
+    let raycast = contact_ray_aabb(&local_ray, Vec2::zero(), obb.half_exts)?;
+    Some(Raycast {
+        toi: raycast.toi,
+        normal: raycast.normal.rotated(obb.angle),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{BodyDesc, ColliderDesc};
+    use crate::math::frac_pi_2;
+    use crate::{to_fp, BodySet, ColliderSet, ContactEvent, PhysicsWorld, Shape};
+
+    fn diamond() -> OBB {
+        OBB::new(Vec2::from(10, 10), frac_pi_2::<FP>() / 2)
+    }
+
+    fn close(a: Vec2, b: Vec2) -> bool {
+        (a - b).length() < to_fp(0.01)
+    }
+
+    #[test]
+    fn separating_axis_against_aabb_and_obb() {
+        let diamond = Shape::from(diamond());
+        let square = Shape::from(AABB {
+            half_exts: Vec2::from(4, 4),
+        });
+        // bounding boxes overlap at the corner, the shapes themselves don't
+        assert!(!diamond.intersects(Vec2::zero(), &square, Vec2::from(14, 14)));
+        assert!(diamond.intersects(Vec2::zero(), &square, Vec2::from(16, 0)));
+
+        let contact = diamond
+            .contact(Vec2::zero(), &square, Vec2::from(16, 0))
+            .unwrap();
+        // the tip of the diamond at x = 14.14 reaches into the square starting at x = 12
+        assert!((contact.depth - to_fp(2.14)).abs() < to_fp(0.01));
+        assert!(close(contact.normal, Vec2::unit_x()));
+
+        // tips of two diamonds stacked on top of each other
+        let contact = diamond
+            .contact(Vec2::from(0, 25), &diamond, Vec2::zero())
+            .unwrap();
+        assert!(contact.normal.y() < to_fp(0));
+        assert!(!diamond.intersects(Vec2::from(0, 30), &diamond, Vec2::zero()));
+    }
+
+    #[test]
+    fn ray_hits_rotated_face() {
+        let ray = Ray {
+            origin: Vec2::from(-30, 0),
+            dir: Vec2::from(60, 0),
+            toi: to_fp(1),
+        };
+        let raycast = contact_ray_obb(&ray, Vec2::zero(), &diamond()).unwrap();
+        let hit = ray.origin + ray.dir * raycast.toi;
+        assert!(close(hit, Vec2::from_float(-14.142, 0.)));
+        assert!(raycast.normal.x() < to_fp(0) && raycast.normal.y().abs() > to_fp(0.7));
+
+        let miss = Ray {
+            origin: Vec2::from(-30, 15),
+            ..ray
+        };
+        assert!(contact_ray_obb(&miss, Vec2::zero(), &diamond()).is_none());
+    }
+
+    #[test]
+    fn reports_collisions_without_resolving_them() {
+        let mut world = PhysicsWorld::<()>::new();
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+        let blade = bodies.insert(BodyDesc::new().make_static().build());
+        colliders.insert(
+            ColliderDesc::new(diamond(), ()).build(blade),
+            &mut bodies,
+            &mut world,
+        );
+        let moving = bodies.insert(
+            BodyDesc::new()
+                .with_position(Vec2::from(-30, 0))
+                .with_velocity(Vec2::from(600, 0))
+                .build(),
+        );
+        colliders.insert(
+            ColliderDesc::new(
+                AABB {
+                    half_exts: Vec2::from(4, 4),
+                },
+                (),
+            )
+            .build(moving),
+            &mut bodies,
+            &mut world,
+        );
+
+        world.step(to_fp(1. / 60.), &mut bodies, &mut colliders);
+        assert!(world.events().is_empty());
+        world.step(to_fp(1. / 60.), &mut bodies, &mut colliders);
+        assert!(matches!(
+            world.events().as_slice(),
+            [ContactEvent::CollisionStarted(..)]
+        ));
+        // moved the full distance through the blade
+        assert!((bodies[moving].position.x() - to_fp(-10)).abs() < to_fp(0.01));
+    }
+}
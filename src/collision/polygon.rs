@@ -0,0 +1,309 @@
+use super::aabb::Contact;
+use super::ray::{Ray, Raycast};
+use super::sat::contact_convex;
+use crate::{Scalar, Vec2, FP};
+
+/// Convex polygon with vertices relative to the collider's position.
+/// Vertices are stored in clockwise order on screen (y axis points down), without collinear points.
+#[derive(Clone, Debug)]
+pub struct ConvexPolygon<N = FP> {
+    vertices: Vec<Vec2<N>>,
+}
+
+impl<N: Scalar> ConvexPolygon<N> {
+    /// Returns `None` if the vertices don't form a convex polygon with non-zero area.
+    /// Both windings are accepted, counter clockwise vertices get reversed.
+    pub fn new(vertices: Vec<Vec2<N>>) -> Option<Self> {
+        let mut polygon = Self { vertices };
+        if polygon.area() < N::zero() {
+            polygon.vertices.reverse();
+        }
+        if polygon.vertices.len() < 3 || !polygon.is_convex() {
+            return None;
+        }
+        Some(polygon)
+    }
+    /// Smallest convex polygon containing all of the points, `None` if they're collinear.
+    pub fn hull(points: &[Vec2<N>]) -> Option<Self> {
+        Self::new(convex_hull(points))
+    }
+    pub fn vertices(&self) -> &[Vec2<N>] {
+        &self.vertices
+    }
+    /// Vertices moved to `position`.
+    pub fn world_vertices(&self, position: Vec2<N>) -> Vec<Vec2<N>> {
+        self.vertices
+            .iter()
+            .map(|vertex| position + *vertex)
+            .collect()
+    }
+    /// Half extents of the smallest `AABB` centered at the collider's position that contains the polygon.
+    pub fn bounding_half_exts(&self) -> Vec2<N> {
+        self.vertices
+            .iter()
+            .fold(Vec2::zero(), |half_exts, vertex| {
+                half_exts.max(vertex.abs())
+            })
+    }
+    // twice the signed area, positive for clockwise polygons
+    fn area(&self) -> N {
+        (0..self.vertices.len()).fold(N::zero(), |area, i| {
+            area + self.vertices[i].cross(self.vertices[(i + 1) % self.vertices.len()])
+        })
+    }
+    fn is_convex(&self) -> bool {
+        let count = self.vertices.len();
+        (0..count).all(|i| {
+            let a = self.vertices[i];
+            let b = self.vertices[(i + 1) % count];
+            let c = self.vertices[(i + 2) % count];
+            (b - a).cross(c - b) > N::zero()
+        })
+    }
+}
+
+// Andrew's monotone chain, clockwise on screen. Collinear points give the two extremes.
+// Sorted lexicographically so the result doesn't depend on the input order.
+pub(crate) fn convex_hull<N: Scalar>(points: &[Vec2<N>]) -> Vec<Vec2<N>> {
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| {
+        (a.x(), a.y())
+            .partial_cmp(&(b.x(), b.y()))
+            .expect("Polygon vertex isn't a number")
+    });
+    points.dedup();
+    if points.len() < 2 {
+        return points;
+    }
+    let mut hull: Vec<Vec2<N>> = Vec::with_capacity(points.len() + 1);
+    for pass in 0..2 {
+        let start = hull.len();
+        for point in points.iter() {
+            while hull.len() >= start + 2 {
+                let a = hull[hull.len() - 2];
+                let b = hull[hull.len() - 1];
+                if (b - a).cross(*point - b) > N::zero() {
+                    break;
+                }
+                hull.pop();
+            }
+            hull.push(*point);
+        }
+        // the last point is the first one of the other pass
+        hull.pop();
+        if pass == 0 {
+            points.reverse();
+        }
+    }
+    hull
+}
+
+pub fn contact_polygon_polygon<N: Scalar>(
+    a_loc: Vec2<N>,
+    a: &ConvexPolygon<N>,
+    b_loc: Vec2<N>,
+    b: &ConvexPolygon<N>,
+) -> Option<Contact<N>> {
+    contact_convex(
+        a_loc,
+        &a.world_vertices(a_loc),
+        b_loc,
+        &b.world_vertices(b_loc),
+    )
+}
+
+// Cyrus-Beck clipping of the ray against every edge.
+// A ray starting inside the polygon hits at 0 with the normal of the closest edge, same as with `AABB`.
+pub fn contact_ray_polygon<N: Scalar>(
+    ray: &Ray<N>,
+    polygon_pos: Vec2<N>,
+    polygon: &ConvexPolygon<N>,
+) -> Option<Raycast<N>> {
+    let vertices = polygon.world_vertices(polygon_pos);
+    let mut t_enter = N::zero();
+    let mut t_exit = ray.toi;
+    let mut enter_normal = None;
+    let mut inside_normal: Option<(N, Vec2<N>)> = None;
+
+    for (i, vertex) in vertices.iter().enumerate() {
+        let edge = vertices[(i + 1) % vertices.len()] - *vertex;
+        // outward for clockwise polygons
+        let normal = -edge.perp().normalized();
+        let distance = normal.dot(ray.origin - *vertex);
+        let speed = normal.dot(ray.dir);
+        if speed == N::zero() {
+            if distance > N::zero() {
+                return None;
+            }
+        } else {
+            let t = -distance / speed;
+            if speed < N::zero() {
+                if t > t_enter {
+                    t_enter = t;
+                    enter_normal = Some(normal);
+                }
+            } else if t < t_exit {
+                t_exit = t;
+            }
+            if t_enter > t_exit {
+                return None;
+            }
+        }
+        if inside_normal.is_none_or(|(closest, _)| distance > closest) {
+            inside_normal = Some((distance, normal));
+        }
+    }
+
+    // no entering edge if the ray started inside or on the boundary
+    let normal = match enter_normal {
+        Some(normal) => normal,
+        None => inside_normal?.1,
+    };
+    Some(Raycast {
+        toi: t_enter,
+        normal,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{BodyDesc, ColliderDesc};
+    use crate::{to_fp, BodySet, ColliderSet, PhysicsWorld, Shape, AABB};
+
+    // right triangle with the right angle at the bottom left
+    fn triangle() -> ConvexPolygon {
+        ConvexPolygon::new(vec![
+            Vec2::from(-10, 10),
+            Vec2::from(10, 10),
+            Vec2::from(-10, -10),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn validates_and_builds_hulls() {
+        assert!(ConvexPolygon::<FP>::new(vec![Vec2::zero(), Vec2::unit_x()]).is_none());
+        // concave arrow head
+        assert!(ConvexPolygon::<FP>::new(vec![
+            Vec2::from(0, 0),
+            Vec2::from(10, 10),
+            Vec2::from(0, 5),
+            Vec2::from(-10, 10),
+        ])
+        .is_none());
+        // counter clockwise input is reversed
+        let polygon = triangle();
+        assert_eq!(polygon.vertices()[0], Vec2::from(-10, -10));
+
+        let hull = ConvexPolygon::<FP>::hull(&[
+            Vec2::from(0, 0),
+            Vec2::from(10, 0),
+            Vec2::from(5, 5),
+            Vec2::from(10, 10),
+            Vec2::from(5, 0),
+            Vec2::from(0, 10),
+        ])
+        .unwrap();
+        assert_eq!(
+            hull.vertices(),
+            &[
+                Vec2::from(0, 0),
+                Vec2::from(10, 0),
+                Vec2::from(10, 10),
+                Vec2::from(0, 10)
+            ]
+        );
+        assert!(
+            ConvexPolygon::<FP>::hull(&[Vec2::zero(), Vec2::one(), Vec2::from(2, 2)]).is_none()
+        );
+    }
+
+    #[test]
+    fn contacts_and_rays() {
+        let triangle = Shape::from(triangle());
+        let square = Shape::from(AABB {
+            half_exts: Vec2::from(4, 4),
+        });
+        // the hypotenuse faces the top right corner
+        assert!(!triangle.intersects(Vec2::zero(), &square, Vec2::from(8, -8)));
+        let contact = triangle
+            .contact(Vec2::zero(), &square, Vec2::from(0, 13))
+            .unwrap();
+        assert_eq!(contact.depth, to_fp(1));
+        assert_eq!(contact.normal, Vec2::unit_y());
+
+        let ray = Ray {
+            origin: Vec2::from(20, 0),
+            dir: Vec2::from(-40, 0),
+            toi: to_fp(1),
+        };
+        let raycast = triangle.ray_contact(Vec2::zero(), &ray).unwrap();
+        assert!((raycast.toi - to_fp(0.5)).abs() < to_fp(0.001));
+        assert!((raycast.normal - Vec2::<FP>::from(1, -1).normalized()).length() < to_fp(0.001));
+
+        let inside = Ray {
+            origin: Vec2::from(-8, 9),
+            ..ray.clone()
+        };
+        let raycast = triangle.ray_contact(Vec2::zero(), &inside).unwrap();
+        assert_eq!((raycast.toi, raycast.normal), (to_fp(0), Vec2::unit_y()));
+
+        let short = Ray {
+            toi: to_fp(0.4),
+            ..ray
+        };
+        assert!(triangle.ray_contact(Vec2::zero(), &short).is_none());
+    }
+
+    #[test]
+    fn queried_through_the_world() {
+        let mut world = PhysicsWorld::<()>::new();
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+        let body = bodies.insert(
+            BodyDesc::new()
+                .with_position(Vec2::from(100, 100))
+                .make_static()
+                .build(),
+        );
+        let handle = colliders
+            .insert(
+                ColliderDesc::new(triangle(), ()).build(body),
+                &mut bodies,
+                &mut world,
+            )
+            .unwrap();
+
+        let hits: Vec<_> = world
+            .overlap_test(
+                Vec2::from(95, 110),
+                Vec2::from(2, 2),
+                u32::MAX,
+                &bodies,
+                &colliders,
+            )
+            .collect();
+        assert_eq!(hits, vec![handle]);
+        let misses = world.overlap_test(
+            Vec2::from(108, 92),
+            Vec2::from(2, 2),
+            u32::MAX,
+            &bodies,
+            &colliders,
+        );
+        assert_eq!(misses.count(), 0);
+
+        let ray = Ray {
+            origin: Vec2::from(50, 105),
+            dir: Vec2::from(100, 0),
+            toi: to_fp(1),
+        };
+        let (hit, raycast) = world
+            .project_ray(&ray, u32::MAX, &bodies, &colliders)
+            .next()
+            .unwrap();
+        assert_eq!(hit, handle);
+        assert_eq!(raycast.normal, -Vec2::unit_x());
+    }
+}
@@ -1,33 +1,36 @@
 use super::intersection_aabb_aabb;
 //use glam::Vec2;
-use crate::{to_fp, Vec2, EPSILON, FP};
+use crate::{Scalar, Vec2, FP};
 
 #[derive(Debug, Clone)]
-pub struct Ray {
-    pub origin: Vec2,
-    pub dir: Vec2,
-    pub toi: FP,
+pub struct Ray<N = FP> {
+    pub origin: Vec2<N>,
+    pub dir: Vec2<N>,
+    pub toi: N,
 }
 
 #[derive(Debug, Clone)]
-pub struct Raycast {
-    pub toi: FP,
-    pub normal: Vec2,
+pub struct Raycast<N = FP> {
+    pub toi: N,
+    pub normal: Vec2<N>,
 }
 
 // ported https://github.com/RandyGaul/cute_headers/blob/master/cute_c2.h#L1427
 #[allow(clippy::float_cmp)]
-pub fn contact_ray_aabb(ray: &Ray, aabb_pos: Vec2, aabb_half_exts: Vec2) -> Option<Raycast> {
+pub fn contact_ray_aabb<N: Scalar>(
+    ray: &Ray<N>,
+    aabb_pos: Vec2<N>,
+    aabb_half_exts: Vec2<N>,
+) -> Option<Raycast<N>> {
     // test if collision with ray is possible by simplifying to aabb2aabb test
-    //let dest = ray.origin + ray.dir * ray.toi;
-    let dest = ray.origin + ray.dir * Vec2::splat(ray.toi);
+    let dest = ray.origin + ray.dir * ray.toi;
     let ray_box_min = ray.origin.min(dest);
     let ray_box_max = ray.origin.max(dest);
-    let ray_box_dim = ray_box_max - ray_box_min;
+    let ray_box_half_dim = (ray_box_max - ray_box_min) * N::from_f64(0.5);
 
     if !intersection_aabb_aabb(
-        ray_box_min + ray_box_dim.mul_scalar(0.5),
-        ray_box_dim.mul_scalar(0.5),
+        ray_box_min + ray_box_half_dim,
+        ray_box_half_dim,
         aabb_pos,
         aabb_half_exts,
     ) {
@@ -35,39 +38,39 @@ pub fn contact_ray_aabb(ray: &Ray, aabb_pos: Vec2, aabb_half_exts: Vec2) -> Opti
     }
 
     let ab = dest - ray.origin;
-    let n = clockwise_90_turn(ab);
+    let n = ab.perp();
     let abs_n = n.abs();
     let d = n.dot(ray.origin - aabb_pos).abs() - abs_n.dot(aabb_half_exts);
-    if d > 0. {
+    if d > N::zero() {
         return None;
     }
 
     let t_left = ray_plane_1d(
         Vec2::new(ray.origin.x(), dest.x()),
-        to_fp(-1.),
+        -N::one(),
         aabb_pos.x() - aabb_half_exts.x(),
     );
     let t_right = ray_plane_1d(
         Vec2::new(ray.origin.x(), dest.x()),
-        to_fp(1.),
+        N::one(),
         aabb_pos.x() + aabb_half_exts.x(),
     );
     let t_top = ray_plane_1d(
         Vec2::new(ray.origin.y(), dest.y()),
-        to_fp(-1),
+        -N::one(),
         aabb_pos.y() - aabb_half_exts.y(),
     );
     let t_bottom = ray_plane_1d(
         Vec2::new(ray.origin.y(), dest.y()),
-        to_fp(1.),
+        N::one(),
         aabb_pos.y() + aabb_half_exts.y(),
     );
 
     // Calculate hit predicate
-    let hit_left = t_left < 1.;
-    let hit_right = t_right < 1.;
-    let hit_top = t_top < 1.;
-    let hit_bottom = t_bottom < 1.;
+    let hit_left = t_left < N::one();
+    let hit_right = t_right < N::one();
+    let hit_top = t_top < N::one();
+    let hit_bottom = t_bottom < N::one();
     let hit = hit_left | hit_right | hit_top | hit_bottom;
 
     if hit {
@@ -78,23 +81,24 @@ pub fn contact_ray_aabb(ray: &Ray, aabb_pos: Vec2, aabb_half_exts: Vec2) -> Opti
         //let t_top = hit_top as u8 as FP * t_top;
         //let t_bottom = hit_bottom as u8 as FP * t_bottom;
 
-        let t_left = to_fp(hit_left as u8) * t_left;
-        let t_right = to_fp(hit_right as u8) * t_right;
-        let t_top = to_fp(hit_top as u8) * t_top;
-        let t_bottom = to_fp(hit_bottom as u8) * t_bottom;
+        let t_left = if hit_left { t_left } else { N::zero() };
+        let t_right = if hit_right { t_right } else { N::zero() };
+        let t_top = if hit_top { t_top } else { N::zero() };
+        let t_bottom = if hit_bottom { t_bottom } else { N::zero() };
 
-        let t_max = t_left.max(t_right).max(t_top).max(t_bottom);
+        let t_max = t_left
+            .partial_max(t_right)
+            .partial_max(t_top)
+            .partial_max(t_bottom);
         let toi = ray.toi * t_max;
 
         // result of multiple calls to `max` so there shouldn't be any issue with floating point error
         let normal = if t_left == t_max {
-            //Vec2::unit_x() * -1.
-            Vec2::from_int(-1, 0)
+            -Vec2::unit_x()
         } else if t_right == t_max {
             Vec2::unit_x()
         } else if t_top == t_max {
-            //Vec2::unit_y() * -1.
-            Vec2::from_int(0, -1)
+            -Vec2::unit_y()
         } else {
             Vec2::unit_y()
         };
@@ -104,30 +108,26 @@ pub fn contact_ray_aabb(ray: &Ray, aabb_pos: Vec2, aabb_half_exts: Vec2) -> Opti
     }
 }
 
-fn clockwise_90_turn(vec: Vec2) -> Vec2 {
-    Vec2::new(-vec.y(), vec.x())
-}
-
 // both ray and plane are one dimensional
 // returns value between 0 to 1 describing time of impact, 1 means no impact
-fn ray_plane_1d(ray_1d: Vec2, normal: FP, plane_1d: FP) -> FP {
-    let d = (ray_1d - Vec2::splat(plane_1d)) * Vec2::splat(normal);
+fn ray_plane_1d<N: Scalar>(ray_1d: Vec2<N>, normal: N, plane_1d: N) -> N {
+    let d = (ray_1d - Vec2::splat(plane_1d)) * normal;
 
     ray_plane_1d_time(d.x(), d.y())
 }
 
-fn ray_plane_1d_time(da: FP, db: FP) -> FP {
+fn ray_plane_1d_time<N: Scalar>(da: N, db: N) -> N {
     // Ray started behind plane
-    if da < 0. {
-        to_fp(0.)
+    if da < N::zero() {
+        N::zero()
     }
     // Ray doesn't intersect the plane
-    else if da * db >= 0. {
-        to_fp(1.)
+    else if da * db >= N::zero() {
+        N::one()
     }
     // Ray is too tiny
-    else if (da - db) < EPSILON() {
-        to_fp(0.)
+    else if (da - db) < N::epsilon() {
+        N::zero()
     } else {
         da / (da - db)
     }
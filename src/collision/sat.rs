@@ -0,0 +1,65 @@
+use super::aabb::Contact;
+use crate::{Scalar, Vec2};
+
+// Separating axis test of two convex polygons given by their vertices in world space (any winding).
+// Touching polygons are treated as intersecting, same as `intersection_aabb_aabb`.
+// The normal points from `a` towards `b`, the contact point is the vertex of `b` deepest inside `a`.
+pub(crate) fn contact_convex<N: Scalar>(
+    a_center: Vec2<N>,
+    a: &[Vec2<N>],
+    b_center: Vec2<N>,
+    b: &[Vec2<N>],
+) -> Option<Contact<N>> {
+    let mut best: Option<(N, Vec2<N>)> = None;
+    for axis in edge_normals(a).chain(edge_normals(b)) {
+        let (a_min, a_max) = project(a, axis);
+        let (b_min, b_max) = project(b, axis);
+        let depth = (a_max - b_min).partial_min(b_max - a_min);
+        if depth < N::zero() {
+            return None;
+        }
+        // strict comparison so the first axis wins ties, keeping results deterministic
+        if best.is_none_or(|(best_depth, _)| depth < best_depth) {
+            best = Some((depth, axis));
+        }
+    }
+    let (depth, axis) = best?;
+    let normal = if (b_center - a_center).dot(axis) < N::zero() {
+        -axis
+    } else {
+        axis
+    };
+    let contact_point = support(b, -normal);
+    Some(Contact::new(depth, normal, contact_point))
+}
+
+// Vertex furthest along `dir`, the first one wins ties
+pub(crate) fn support<N: Scalar>(vertices: &[Vec2<N>], dir: Vec2<N>) -> Vec2<N> {
+    let mut best = vertices[0];
+    let mut best_distance = best.dot(dir);
+    for vertex in &vertices[1..] {
+        let distance = vertex.dot(dir);
+        if distance > best_distance {
+            best = *vertex;
+            best_distance = distance;
+        }
+    }
+    best
+}
+
+fn edge_normals<N: Scalar>(vertices: &[Vec2<N>]) -> impl Iterator<Item = Vec2<N>> + '_ {
+    (0..vertices.len())
+        .map(move |i| vertices[(i + 1) % vertices.len()] - vertices[i])
+        .filter(|edge| *edge != Vec2::zero())
+        .map(|edge| edge.perp().normalized())
+}
+
+fn project<N: Scalar>(vertices: &[Vec2<N>], axis: Vec2<N>) -> (N, N) {
+    let first = vertices[0].dot(axis);
+    vertices[1..]
+        .iter()
+        .map(|vertex| vertex.dot(axis))
+        .fold((first, first), |(min, max), distance| {
+            (min.partial_min(distance), max.partial_max(distance))
+        })
+}
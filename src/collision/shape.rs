@@ -0,0 +1,203 @@
+use super::aabb::{contact_aabb_aabb, intersection_aabb_aabb, Contact, AABB};
+use super::capsule::{
+    contact_capsule_capsule, contact_capsule_convex, contact_ray_capsule, reach_capsule_aabb,
+    reach_capsule_capsule, Capsule,
+};
+use super::chain::{contact_chain_shape, contact_ray_chain, SegmentChain};
+use super::obb::{contact_ray_obb, OBB};
+use super::polygon::{contact_ray_polygon, ConvexPolygon};
+use super::ray::{contact_ray_aabb, Ray, Raycast};
+use super::sat::contact_convex;
+use crate::{Scalar, Vec2, FP};
+
+/// Shape of a `Collider`, positioned by its center.
+#[derive(Clone, Debug)]
+pub enum Shape<N = FP> {
+    AABB(AABB<N>),
+    OBB(OBB<N>),
+    Polygon(ConvexPolygon<N>),
+    Capsule(Capsule<N>),
+    /// Only allowed on static bodies
+    Chain(SegmentChain<N>),
+}
+
+impl<N: Scalar> Shape<N> {
+    /// Half extents of the smallest `AABB` centered at the shape's position that contains the shape.
+    pub fn half_exts(&self) -> Vec2<N> {
+        match self {
+            Shape::AABB(aabb) => aabb.half_exts,
+            Shape::OBB(obb) => obb.bounding_half_exts(),
+            Shape::Polygon(polygon) => polygon.bounding_half_exts(),
+            Shape::Capsule(capsule) => capsule.bounding_half_exts(),
+            Shape::Chain(chain) => chain.bounding_half_exts(),
+        }
+    }
+    pub fn as_aabb(&self) -> Option<&AABB<N>> {
+        match self {
+            Shape::AABB(aabb) => Some(aabb),
+            _ => None,
+        }
+    }
+    /// Vertices of the shape moved to `position`, in clockwise order on screen. `None` for capsules and chains.
+    pub fn vertices(&self, position: Vec2<N>) -> Option<Vec<Vec2<N>>> {
+        match self {
+            Shape::AABB(aabb) => Some(OBB::from(*aabb).corners(position).to_vec()),
+            Shape::OBB(obb) => Some(obb.corners(position).to_vec()),
+            Shape::Polygon(polygon) => Some(polygon.world_vertices(position)),
+            Shape::Capsule(_) | Shape::Chain(_) => None,
+        }
+    }
+    // Convex core and the radius rounding it, a segment for capsules. `None` for chains.
+    pub(crate) fn rounded_parts(&self, position: Vec2<N>) -> Option<(Vec<Vec2<N>>, N)> {
+        match self {
+            Shape::Capsule(capsule) => {
+                let (top, bottom) = capsule.segment(position);
+                Some((vec![top, bottom], capsule.radius))
+            }
+            _ => Some((self.vertices(position)?, N::zero())),
+        }
+    }
+    pub fn intersects(&self, position: Vec2<N>, other: &Shape<N>, other_position: Vec2<N>) -> bool {
+        match (self, other) {
+            (Shape::AABB(a), Shape::AABB(b)) => {
+                intersection_aabb_aabb(position, a.half_exts, other_position, b.half_exts)
+            }
+            _ => self.contact(position, other, other_position).is_some(),
+        }
+    }
+    /// Deepest contact, its normal points from `self` towards `other`.
+    pub fn contact(
+        &self,
+        position: Vec2<N>,
+        other: &Shape<N>,
+        other_position: Vec2<N>,
+    ) -> Option<Contact<N>> {
+        match (self, other) {
+            (Shape::AABB(a), Shape::AABB(b)) => {
+                contact_aabb_aabb(position, a.half_exts, other_position, b.half_exts)
+                    .map(|manifold| manifold.best_contact().clone())
+            }
+            (Shape::Chain(_), Shape::Chain(_)) => None,
+            (Shape::Chain(chain), _) => contact_chain_shape(position, chain, other_position, other),
+            (_, Shape::Chain(chain)) => {
+                contact_chain_shape(other_position, chain, position, self).map(flipped)
+            }
+            (Shape::Capsule(a), Shape::Capsule(b)) => {
+                contact_capsule_capsule(position, a, other_position, b)
+            }
+            (Shape::Capsule(capsule), _) => contact_capsule_convex(
+                position,
+                capsule,
+                other_position,
+                &other.vertices(other_position)?,
+            ),
+            (_, Shape::Capsule(capsule)) => {
+                contact_capsule_convex(other_position, capsule, position, &self.vertices(position)?)
+                    .map(flipped)
+            }
+            _ => contact_convex(
+                position,
+                &self.vertices(position)?,
+                other_position,
+                &other.vertices(other_position)?,
+            ),
+        }
+    }
+    pub fn ray_contact(&self, position: Vec2<N>, ray: &Ray<N>) -> Option<Raycast<N>> {
+        match self {
+            Shape::AABB(aabb) => contact_ray_aabb(ray, position, aabb.half_exts),
+            Shape::OBB(obb) => contact_ray_obb(ray, position, obb),
+            Shape::Polygon(polygon) => contact_ray_polygon(ray, position, polygon),
+            Shape::Capsule(capsule) => contact_ray_capsule(ray, position, capsule),
+            Shape::Chain(chain) => contact_ray_chain(ray, position, chain),
+        }
+    }
+    // Only pairs of `AABB`s and `Capsule`s are resolved by the axis separated solver,
+    // `self` is shrunk by `tolerance` so resting contacts don't count as penetration.
+    pub(crate) fn penetrates(
+        &self,
+        position: Vec2<N>,
+        other: &Shape<N>,
+        other_position: Vec2<N>,
+        tolerance: N,
+    ) -> bool {
+        match other {
+            Shape::AABB(_) | Shape::Capsule(_) => self
+                .shrunk(tolerance)
+                .is_some_and(|shrunk| shrunk.intersects(position, other, other_position)),
+            _ => false,
+        }
+    }
+    // `AABB`s and `Capsule`s moved in by `tolerance` on every side, `None` for the other shapes.
+    pub(crate) fn shrunk(&self, tolerance: N) -> Option<Shape<N>> {
+        match self {
+            Shape::AABB(aabb) => Some(Shape::AABB(AABB {
+                half_exts: aabb.half_exts - Vec2::splat(tolerance),
+            })),
+            Shape::Capsule(capsule) => Some(Shape::Capsule(Capsule::new(
+                capsule.half_height,
+                capsule.radius - tolerance,
+            ))),
+            _ => None,
+        }
+    }
+    // Sum of both extents along each axis for a pair resolved by the solver.
+    pub(crate) fn reach(
+        &self,
+        position: Vec2<N>,
+        other: &Shape<N>,
+        other_position: Vec2<N>,
+    ) -> Vec2<N> {
+        match (self, other) {
+            (Shape::Capsule(a), Shape::Capsule(b)) => {
+                reach_capsule_capsule(position, a, other_position, b)
+            }
+            (Shape::Capsule(capsule), Shape::AABB(aabb)) => {
+                reach_capsule_aabb(position, capsule, other_position, aabb.half_exts)
+            }
+            (Shape::AABB(aabb), Shape::Capsule(capsule)) => {
+                reach_capsule_aabb(other_position, capsule, position, aabb.half_exts)
+            }
+            _ => self.half_exts() + other.half_exts(),
+        }
+    }
+}
+
+// Same contact seen from the other shape, the contact point moves to the deepest point of the first one.
+fn flipped<N: Scalar>(contact: Contact<N>) -> Contact<N> {
+    Contact::new(
+        contact.depth,
+        -contact.normal,
+        contact.contact_point + contact.normal * contact.depth,
+    )
+}
+
+impl<N> From<AABB<N>> for Shape<N> {
+    fn from(aabb: AABB<N>) -> Self {
+        Shape::AABB(aabb)
+    }
+}
+
+impl<N> From<OBB<N>> for Shape<N> {
+    fn from(obb: OBB<N>) -> Self {
+        Shape::OBB(obb)
+    }
+}
+
+impl<N> From<Capsule<N>> for Shape<N> {
+    fn from(capsule: Capsule<N>) -> Self {
+        Shape::Capsule(capsule)
+    }
+}
+
+impl<N> From<ConvexPolygon<N>> for Shape<N> {
+    fn from(polygon: ConvexPolygon<N>) -> Self {
+        Shape::Polygon(polygon)
+    }
+}
+
+impl<N> From<SegmentChain<N>> for Shape<N> {
+    fn from(chain: SegmentChain<N>) -> Self {
+        Shape::Chain(chain)
+    }
+}
@@ -0,0 +1,294 @@
+use super::collision::{Interaction, Ray, Raycast, Shape};
+use super::object::{BodySet, BodyStatus, ColliderSet, ColliderState};
+use super::world::PhysicsWorld;
+use crate::{Scalar, Vec2, FP};
+use petgraph::visit::EdgeRef;
+
+/// RGBA color with components in the `0..=1` range.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DebugColor {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl DebugColor {
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+/// Backend for `debug_draw`, implement it for the renderer of your choice.
+/// All positions are given in world space.
+pub trait DebugDraw<N: Scalar = FP> {
+    /// `position` is the center of the rectangle
+    fn draw_rect(&mut self, position: Vec2<N>, half_exts: Vec2<N>, color: DebugColor);
+    fn draw_line(&mut self, from: Vec2<N>, to: Vec2<N>, color: DebugColor);
+    fn draw_point(&mut self, position: Vec2<N>, color: DebugColor);
+    fn draw_text(&mut self, position: Vec2<N>, text: &str, color: DebugColor);
+    /// Closed convex outline, defaults to a line per edge
+    fn draw_polygon(&mut self, vertices: &[Vec2<N>], color: DebugColor) {
+        for (i, from) in vertices.iter().enumerate() {
+            self.draw_line(*from, vertices[(i + 1) % vertices.len()], color);
+        }
+    }
+    /// Outline, defaults to a polygon with 16 sides
+    fn draw_circle(&mut self, center: Vec2<N>, radius: N, color: DebugColor) {
+        let step = crate::math::tau::<N>() / N::from_i32(16);
+        let vertices: Vec<_> = (0..16)
+            .map(|i| center + Vec2::from_angle(step * N::from_i32(i)) * radius)
+            .collect();
+        self.draw_polygon(&vertices, color);
+    }
+    /// Defaults to a line with two short lines forming the head
+    fn draw_arrow(&mut self, from: Vec2<N>, to: Vec2<N>, color: DebugColor) {
+        self.draw_line(from, to, color);
+        let dir = to - from;
+        let length = dir.length();
+        if length == N::zero() {
+            return;
+        }
+        let two = N::from_i32(2);
+        let head = N::from_i32(6).partial_min(length / two);
+        let back = to - dir * (head / length);
+        let side = dir.perp() * (head / two / length);
+        self.draw_line(to, back + side, color);
+        self.draw_line(to, back - side, color);
+    }
+}
+
+/// Colors and toggles used by `debug_draw`.
+#[derive(Clone, Debug)]
+pub struct DebugDrawStyle<N = FP> {
+    pub static_solid: DebugColor,
+    pub kinematic_solid: DebugColor,
+    pub sensor: DebugColor,
+    pub contact_normal: DebugColor,
+    pub overlap: DebugColor,
+    pub ray: DebugColor,
+    pub ray_hit: DebugColor,
+    pub body_origin: DebugColor,
+    pub velocity: DebugColor,
+    /// Length of the drawn contact and raycast normals
+    pub normal_length: N,
+    /// Multiplier turning velocity into the length of its arrow
+    pub velocity_scale: N,
+    /// Whether to mark the position of every body
+    pub body_origins: bool,
+    /// Whether to draw the velocity of every kinematic body as an arrow
+    pub velocity_arrows: bool,
+    /// Whether to label every kinematic body with its velocity
+    pub velocity_labels: bool,
+}
+
+impl<N: Scalar> Default for DebugDrawStyle<N> {
+    fn default() -> Self {
+        Self {
+            static_solid: DebugColor::new(0.3, 0.3, 0.8, 1.),
+            kinematic_solid: DebugColor::new(0., 0.47, 0.95, 1.),
+            sensor: DebugColor::new(0.99, 0.98, 0., 1.),
+            contact_normal: DebugColor::new(0.9, 0.16, 0.22, 1.),
+            overlap: DebugColor::new(1., 0.63, 0., 1.),
+            ray: DebugColor::new(0.5, 0.5, 0.5, 1.),
+            ray_hit: DebugColor::new(0., 0.89, 0.19, 1.),
+            body_origin: DebugColor::new(0.16, 0.16, 0.16, 1.),
+            velocity: DebugColor::new(0.78, 0.48, 1., 1.),
+            normal_length: N::from_i32(16),
+            velocity_scale: N::from_f64(0.25),
+            body_origins: false,
+            velocity_arrows: false,
+            velocity_labels: false,
+        }
+    }
+}
+
+/// Draws every collider, the active interactions stored in the `CollisionGraph` and the given raycasts.
+///
+/// Colliders are colored by their state and their body's status, collisions are drawn as contact normals
+/// starting at the center of the first collider, overlaps as lines connecting the centers of both colliders.
+/// `raycasts` pairs every ray with its result, e.g. collected from `PhysicsWorld::project_ray`.
+pub fn debug_draw<T: Copy, N: Scalar>(
+    draw: &mut impl DebugDraw<N>,
+    style: &DebugDrawStyle<N>,
+    world: &PhysicsWorld<T, N>,
+    bodies: &BodySet<N>,
+    colliders: &ColliderSet<T, N>,
+    raycasts: &[(Ray<N>, Option<Raycast<N>>)],
+) {
+    let collider_center = |handle| {
+        let collider = &colliders[handle];
+        bodies[collider.owner].position + collider.offset
+    };
+
+    for (_, collider) in colliders.iter() {
+        let body = &bodies[collider.owner];
+        let color = match (collider.state, body.status) {
+            (ColliderState::Sensor, _) => style.sensor,
+            (ColliderState::Solid, BodyStatus::Static) => style.static_solid,
+            (ColliderState::Solid, BodyStatus::Kinematic) => style.kinematic_solid,
+        };
+        let position = body.position + collider.offset;
+        match &collider.shape {
+            Shape::AABB(aabb) => draw.draw_rect(position, aabb.half_exts, color),
+            Shape::Capsule(capsule) => {
+                let (top, bottom) = capsule.segment(position);
+                let side = Vec2::new(capsule.radius, N::zero());
+                draw.draw_circle(top, capsule.radius, color);
+                draw.draw_circle(bottom, capsule.radius, color);
+                draw.draw_line(top - side, bottom - side, color);
+                draw.draw_line(top + side, bottom + side, color);
+            }
+            Shape::Chain(chain) => {
+                for (start, end, _) in chain.segments(position) {
+                    draw.draw_line(start, end, color);
+                }
+            }
+            shape => draw.draw_polygon(&shape.vertices(position).unwrap_or_default(), color),
+        }
+    }
+
+    let graph = &world.collision_graph.src;
+    for edge in graph.edge_references() {
+        let handle1 = graph[edge.source()];
+        let handle2 = graph[edge.target()];
+        match &edge.weight().interaction {
+            Some(Interaction::Collision(info)) => {
+                let from = collider_center(handle1);
+                draw.draw_line(
+                    from,
+                    from + info.normal * style.normal_length,
+                    style.contact_normal,
+                );
+            }
+            Some(Interaction::Overlap) => {
+                draw.draw_line(
+                    collider_center(handle1),
+                    collider_center(handle2),
+                    style.overlap,
+                );
+            }
+            None => {}
+        }
+    }
+
+    for (ray, raycast) in raycasts.iter() {
+        let end = ray.origin + ray.dir * ray.toi;
+        draw.draw_line(ray.origin, end, style.ray);
+        if let Some(raycast) = raycast {
+            let hit = ray.origin + ray.dir * raycast.toi;
+            draw.draw_line(ray.origin, hit, style.ray_hit);
+            draw.draw_line(
+                hit,
+                hit + raycast.normal * style.normal_length,
+                style.contact_normal,
+            );
+            draw.draw_point(hit, style.ray_hit);
+        }
+    }
+
+    for (_, body) in bodies.iter() {
+        if style.body_origins {
+            draw.draw_point(body.position, style.body_origin);
+        }
+        if let BodyStatus::Static = body.status {
+            continue;
+        }
+        if style.velocity_arrows {
+            draw.draw_arrow(
+                body.position,
+                body.position + body.velocity * style.velocity_scale,
+                style.velocity,
+            );
+        }
+        if style.velocity_labels {
+            draw.draw_text(
+                body.position,
+                &body.velocity.to_string(),
+                style.kinematic_solid,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{BodyDesc, ColliderDesc};
+    use crate::{to_fp, AABB};
+
+    #[derive(Default)]
+    struct Recorder {
+        rects: usize,
+        lines: usize,
+        points: usize,
+    }
+
+    impl DebugDraw for Recorder {
+        fn draw_rect(&mut self, _: Vec2, _: Vec2, _: DebugColor) {
+            self.rects += 1;
+        }
+        fn draw_line(&mut self, _: Vec2, _: Vec2, _: DebugColor) {
+            self.lines += 1;
+        }
+        fn draw_point(&mut self, _: Vec2, _: DebugColor) {
+            self.points += 1;
+        }
+        fn draw_text(&mut self, _: Vec2, _: &str, _: DebugColor) {}
+    }
+
+    #[test]
+    fn draws_colliders_contacts_and_rays() {
+        let mut world = PhysicsWorld::new();
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+        let shape = AABB {
+            half_exts: Vec2::from(8, 8),
+        };
+
+        let moving = bodies.insert(BodyDesc::new().with_velocity(Vec2::from(0, 60)).build());
+        colliders.insert(
+            ColliderDesc::new(shape, ()).build(moving),
+            &mut bodies,
+            &mut world,
+        );
+        let ground = bodies.insert(
+            BodyDesc::new()
+                .with_position(Vec2::from(0, 16))
+                .make_static()
+                .build(),
+        );
+        colliders.insert(
+            ColliderDesc::new(shape, ()).build(ground),
+            &mut bodies,
+            &mut world,
+        );
+        world.step(to_fp(1. / 60.), &mut bodies, &mut colliders);
+
+        let ray = Ray {
+            origin: Vec2::from(0, -32),
+            dir: Vec2::from(0, 64),
+            toi: to_fp(1),
+        };
+        let raycasts: Vec<_> = world
+            .project_ray(&ray, u32::MAX, &bodies, &colliders)
+            .map(|(_, raycast)| (ray.clone(), Some(raycast)))
+            .collect();
+        assert_eq!(raycasts.len(), 2);
+
+        let mut recorder = Recorder::default();
+        let style = DebugDrawStyle::default();
+        debug_draw(
+            &mut recorder,
+            &style,
+            &world,
+            &bodies,
+            &colliders,
+            &raycasts,
+        );
+        assert_eq!(recorder.rects, 2);
+        assert_eq!(recorder.points, raycasts.len());
+        // one contact normal plus three lines per raycast
+        assert_eq!(recorder.lines, 1 + 3 * raycasts.len());
+    }
+}
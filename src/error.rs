@@ -0,0 +1,114 @@
+use crate::{BodyHandle, ColliderHandle};
+use std::fmt;
+
+/// Error returned by the `try_` functions instead of panicking on misused handles.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PhysicsError {
+    /// The body was removed or belongs to another `BodySet`
+    BodyNotFound(BodyHandle),
+    /// The collider was removed or belongs to another `ColliderSet`
+    ColliderNotFound(ColliderHandle),
+    /// The collider isn't a node of the `CollisionGraph`, e.g. it was inserted with another `PhysicsWorld`
+    ColliderNotInGraph(ColliderHandle),
+    /// `SegmentChain` colliders can only be attached to static bodies
+    ChainOnKinematicBody(BodyHandle),
+}
+
+impl fmt::Display for PhysicsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PhysicsError::BodyNotFound(handle) => write!(f, "body {:?} doesn't exist", handle),
+            PhysicsError::ColliderNotFound(handle) => {
+                write!(f, "collider {:?} doesn't exist", handle)
+            }
+            PhysicsError::ColliderNotInGraph(handle) => {
+                write!(f, "collider {:?} isn't in the collision graph", handle)
+            }
+            PhysicsError::ChainOnKinematicBody(handle) => {
+                write!(f, "segment chain attached to kinematic body {:?}", handle)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PhysicsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{BodyDesc, ColliderDesc};
+    use crate::{BodySet, ColliderSet, PhysicsWorld, SegmentChain, Vec2, AABB};
+
+    #[test]
+    fn stale_handles_are_reported() {
+        let mut world = PhysicsWorld::<()>::new();
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+        let body = bodies.insert(BodyDesc::new().build());
+        let desc = ColliderDesc::new(
+            AABB {
+                half_exts: Vec2::from(8, 8),
+            },
+            (),
+        );
+        let collider = colliders
+            .try_insert(desc.clone().build(body), &mut bodies, &mut world)
+            .unwrap();
+        let chain = SegmentChain::new(vec![Vec2::zero(), Vec2::from(10, 0)]).unwrap();
+        assert_eq!(
+            colliders.try_insert(
+                desc.clone().with_shape(chain).build(body),
+                &mut bodies,
+                &mut world
+            ),
+            Err(PhysicsError::ChainOnKinematicBody(body))
+        );
+
+        world
+            .try_remove_body(body, &mut bodies, &mut colliders)
+            .unwrap();
+        assert_eq!(
+            world.try_remove_body(body, &mut bodies, &mut colliders),
+            Err(PhysicsError::BodyNotFound(body))
+        );
+        assert_eq!(
+            world.try_remove_collider(collider, &mut bodies, &mut colliders),
+            Err(PhysicsError::ColliderNotFound(collider))
+        );
+        assert!(world.try_interactions_of(collider).is_err());
+        assert!(world.try_collisions_of(collider).is_err());
+        assert!(world.try_overlaps_of(collider).is_err());
+        assert_eq!(
+            world.collision_graph.try_update_edge(collider, collider),
+            Err(PhysicsError::ColliderNotInGraph(collider))
+        );
+        assert!(colliders.try_get(collider).is_err());
+        assert_eq!(
+            colliders.try_insert(desc.clone().build(body), &mut bodies, &mut world),
+            Err(PhysicsError::BodyNotFound(body))
+        );
+
+        // inserted into another world
+        let mut other_world = PhysicsWorld::<()>::new();
+        let body = bodies.insert(BodyDesc::new().build());
+        let foreign = colliders
+            .try_insert(desc.build(body), &mut bodies, &mut other_world)
+            .unwrap();
+        assert_eq!(
+            world.try_remove_collider(foreign, &mut bodies, &mut colliders),
+            Err(PhysicsError::ColliderNotInGraph(foreign))
+        );
+        assert!(colliders.contains(foreign));
+        // checked before anything is removed
+        assert_eq!(
+            world.try_remove_body(body, &mut bodies, &mut colliders),
+            Err(PhysicsError::ColliderNotInGraph(foreign))
+        );
+        assert!(bodies.contains(body));
+        other_world.step(crate::to_fp(1. / 60.), &mut bodies, &mut colliders);
+        assert_eq!(
+            PhysicsError::ColliderNotInGraph(foreign).to_string(),
+            format!("collider {:?} isn't in the collision graph", foreign)
+        );
+    }
+}
@@ -1,7 +1,10 @@
-use super::object::{Collider, ColliderHandle, ColliderState};
+use super::collision::Interaction;
+use super::object::{BodyHandle, Collider, ColliderHandle, ColliderState};
+use crate::{Scalar, Vec2, FP};
 
 /// Event generated by the collision engine.  
-/// In case of an overlap between a solid body and sensor the solid body is guaranteed to be the first handle.
+/// In case of an overlap between a solid body and sensor the solid body is guaranteed to be the first handle,
+/// otherwise the handles are in ascending order.
 #[derive(Debug, Clone, Copy)]
 pub enum ContactEvent<T> {
     OverlapStarted(ColliderHandle, ColliderHandle, T, T),
@@ -10,14 +13,35 @@ pub enum ContactEvent<T> {
     CollisionEnded(ColliderHandle, ColliderHandle, T, T),
 }
 
+/// Body-level event of a sensor collider, with the sensor's tag.  
+/// Sent once per body, when the first of its colliders starts or the last one stops overlapping the sensor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensorEvent<T> {
+    Entered(ColliderHandle, BodyHandle, T),
+    Exited(ColliderHandle, BodyHandle, T),
+}
+
+/// Body-level collision event, sent once per pair of bodies however many of their colliders touch.  
+/// Carries the normal of the touching collider pair with the lowest handles, pointing from the first body towards the second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BodyContactEvent<N = FP> {
+    Started(BodyHandle, BodyHandle, Vec2<N>),
+    Ended(BodyHandle, BodyHandle, Vec2<N>),
+}
+
 impl<T: Copy> ContactEvent<T> {
-    pub fn new(
+    pub fn new<N: Scalar>(
         h1: ColliderHandle,
-        collider1: &Collider<T>,
+        collider1: &Collider<T, N>,
         h2: ColliderHandle,
-        collider2: &Collider<T>,
+        collider2: &Collider<T, N>,
     ) -> ContactEvent<T> {
         use ColliderState::*;
+        let ((h1, collider1), (h2, collider2)) = if h1 <= h2 {
+            ((h1, collider1), (h2, collider2))
+        } else {
+            ((h2, collider2), (h1, collider1))
+        };
         match (&collider1.state, &collider2.state) {
             (Solid, Solid) => {
                 ContactEvent::CollisionStarted(h1, h2, collider1.user_tag, collider2.user_tag)
@@ -33,6 +57,38 @@ impl<T: Copy> ContactEvent<T> {
             }
         }
     }
+    // kind of the event follows the interaction, so solid pairs which only overlap send overlap events
+    // and interactions which started before a collider's state changed end with the right kind
+    pub(crate) fn with_interaction<N: Scalar>(
+        h1: ColliderHandle,
+        collider1: &Collider<T, N>,
+        h2: ColliderHandle,
+        collider2: &Collider<T, N>,
+        interaction: &Interaction<N>,
+    ) -> ContactEvent<T> {
+        match (interaction, Self::new(h1, collider1, h2, collider2)) {
+            (Interaction::Overlap, ContactEvent::CollisionStarted(h1, h2, t1, t2)) => {
+                ContactEvent::OverlapStarted(h1, h2, t1, t2)
+            }
+            // the solid collider is first if the other one turned into a sensor, collisions are in ascending order
+            (Interaction::Collision(_), ContactEvent::OverlapStarted(h1, h2, t1, t2)) => {
+                if h1 <= h2 {
+                    ContactEvent::CollisionStarted(h1, h2, t1, t2)
+                } else {
+                    ContactEvent::CollisionStarted(h2, h1, t2, t1)
+                }
+            }
+            (_, event) => event,
+        }
+    }
+    /// Handles of the colliders in ascending order.
+    pub fn pair(&self) -> (ColliderHandle, ColliderHandle) {
+        let (Self::OverlapStarted(h1, h2, ..)
+        | Self::OverlapEnded(h1, h2, ..)
+        | Self::CollisionStarted(h1, h2, ..)
+        | Self::CollisionEnded(h1, h2, ..)) = self;
+        (*h1.min(h2), *h1.max(h2))
+    }
     // changes started events into ended
     pub(crate) fn into_finished(self) -> ContactEvent<T> {
         match self {
@@ -0,0 +1,269 @@
+use crate::builder::{BodyDesc, ColliderDesc};
+use crate::{
+    to_fp, BodySet, ColliderHandle, ColliderSet, PhysicsWorld, Tilemap, TilemapDesc, Vec2, AABB,
+};
+use fxhash::FxHashMap;
+use serde::Deserialize;
+use std::fmt;
+
+/// Error generated while importing a LDtk project.
+#[derive(Debug)]
+pub enum LdtkError {
+    /// The file isn't valid LDtk JSON
+    Json(serde_json::Error),
+    /// Level is stored in a separate file, only levels embedded in the project are supported
+    ExternalLevel(String),
+    /// IntGrid layer's values don't match its dimensions
+    InvalidIntGrid(String),
+}
+
+impl fmt::Display for LdtkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LdtkError::Json(error) => write!(f, "invalid LDtk JSON: {}", error),
+            LdtkError::ExternalLevel(level) => {
+                write!(f, "level {:?} isn't embedded in the project", level)
+            }
+            LdtkError::InvalidIntGrid(layer) => {
+                write!(f, "IntGrid layer {:?} doesn't match its dimensions", layer)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LdtkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LdtkError::Json(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for LdtkError {
+    fn from(error: serde_json::Error) -> Self {
+        LdtkError::Json(error)
+    }
+}
+
+/// Colliders created from a single LDtk level.
+#[derive(Debug, Clone)]
+pub struct LdtkLevel<T> {
+    pub identifier: String,
+    pub iid: String,
+    /// One tilemap per IntGrid layer containing at least one solid cell, paired with the layer's identifier
+    pub tilemaps: Vec<(String, Tilemap<T>)>,
+}
+
+/// Result of importing a LDtk project.
+#[derive(Debug, Clone)]
+pub struct LdtkProject<T> {
+    pub levels: Vec<LdtkLevel<T>>,
+    /// Sensor colliders created from entity instances, keyed by the entity's IID
+    pub entities: FxHashMap<String, ColliderHandle>,
+}
+
+/// Populates the world with colliders described by a LDtk project.
+///
+/// Every non-zero cell of an IntGrid layer is solid, each layer becomes a `Tilemap` placed at the level's world position.
+/// Every entity instance with a non-zero size becomes a static body with a single sensor collider covering its bounds.
+///
+/// `user_tag` receives the identifier of the layer or entity and turns it into the collider's tag.
+pub fn load_ldtk_json<T: Copy>(
+    json: &str,
+    world: &mut PhysicsWorld<T>,
+    bodies: &mut BodySet,
+    colliders: &mut ColliderSet<T>,
+    mut user_tag: impl FnMut(&str) -> T,
+) -> Result<LdtkProject<T>, LdtkError> {
+    let project: Project = serde_json::from_str(json)?;
+
+    let mut result = LdtkProject {
+        levels: Vec::with_capacity(project.levels.len()),
+        entities: FxHashMap::default(),
+    };
+
+    for level in project.levels.iter() {
+        let layers = level
+            .layer_instances
+            .as_ref()
+            .ok_or_else(|| LdtkError::ExternalLevel(level.identifier.clone()))?;
+        let level_position = Vec2::from(level.world_x, level.world_y);
+        let mut tilemaps = Vec::new();
+
+        for layer in layers.iter() {
+            let layer_position =
+                level_position + Vec2::from(layer.px_total_offset_x, layer.px_total_offset_y);
+            match layer.kind.as_str() {
+                "IntGrid" => {
+                    if layer.int_grid_csv.len() != layer.c_wid * layer.c_hei {
+                        return Err(LdtkError::InvalidIntGrid(layer.identifier.clone()));
+                    }
+                    let cells: Vec<bool> = layer.int_grid_csv.iter().map(|&v| v != 0).collect();
+                    if !cells.contains(&true) {
+                        continue;
+                    }
+                    let tilemap = TilemapDesc::new(
+                        layer.c_wid,
+                        layer.c_hei,
+                        Vec2::splat(to_fp(layer.grid_size)),
+                        user_tag(&layer.identifier),
+                    )
+                    .with_position(layer_position)
+                    .with_cells(cells)
+                    .build(world, bodies, colliders);
+                    tilemaps.push((layer.identifier.clone(), tilemap));
+                }
+                "Entities" => {
+                    for entity in layer.entity_instances.iter() {
+                        if entity.width <= 0. || entity.height <= 0. {
+                            continue;
+                        }
+                        let size = Vec2::from(entity.width, entity.height);
+                        // `px` points at the pivot, which is given as a fraction of the size
+                        let top_left = Vec2::from(entity.px[0], entity.px[1])
+                            - Vec2::from(entity.pivot[0], entity.pivot[1]) * size;
+                        let half_exts = size.mul_scalar(0.5);
+                        let body = bodies.insert(
+                            BodyDesc::new()
+                                .with_position(layer_position + top_left + half_exts)
+                                .make_static()
+                                .build(),
+                        );
+                        let collider =
+                            ColliderDesc::new(AABB { half_exts }, user_tag(&entity.identifier))
+                                .sensor()
+                                .build(body);
+                        let collider = colliders
+                            .insert(collider, bodies, world)
+                            .expect("LDtk import: freshly inserted body is missing");
+                        result.entities.insert(entity.iid.clone(), collider);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        result.levels.push(LdtkLevel {
+            identifier: level.identifier.clone(),
+            iid: level.iid.clone(),
+            tilemaps,
+        });
+    }
+    Ok(result)
+}
+
+#[derive(Deserialize)]
+struct Project {
+    #[serde(default)]
+    levels: Vec<Level>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Level {
+    identifier: String,
+    #[serde(default)]
+    iid: String,
+    #[serde(default)]
+    world_x: f64,
+    #[serde(default)]
+    world_y: f64,
+    layer_instances: Option<Vec<Layer>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Layer {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__type")]
+    kind: String,
+    #[serde(rename = "__cWid")]
+    c_wid: usize,
+    #[serde(rename = "__cHei")]
+    c_hei: usize,
+    #[serde(rename = "__gridSize")]
+    grid_size: f64,
+    #[serde(rename = "__pxTotalOffsetX", default)]
+    px_total_offset_x: f64,
+    #[serde(rename = "__pxTotalOffsetY", default)]
+    px_total_offset_y: f64,
+    #[serde(default)]
+    int_grid_csv: Vec<i64>,
+    #[serde(default)]
+    entity_instances: Vec<Entity>,
+}
+
+#[derive(Deserialize)]
+struct Entity {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    iid: String,
+    px: [f64; 2],
+    #[serde(rename = "__pivot", default)]
+    pivot: [f64; 2],
+    width: f64,
+    height: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ColliderState;
+
+    const PROJECT: &str = r#"{
+        "levels": [{
+            "identifier": "Level_0", "iid": "a1", "worldX": 256, "worldY": 0,
+            "layerInstances": [
+                { "__identifier": "Entities", "__type": "Entities", "__cWid": 4, "__cHei": 2, "__gridSize": 8,
+                  "__pxTotalOffsetX": 0, "__pxTotalOffsetY": 0,
+                  "entityInstances": [
+                    { "__identifier": "Door", "iid": "e-door", "px": [16, 16], "__pivot": [0.5, 1],
+                      "width": 8, "height": 16 }
+                  ] },
+                { "__identifier": "Collisions", "__type": "IntGrid", "__cWid": 4, "__cHei": 2, "__gridSize": 8,
+                  "__pxTotalOffsetX": 0, "__pxTotalOffsetY": 0,
+                  "intGridCsv": [0, 0, 0, 1, 2, 2, 2, 1] }
+            ]
+        }]
+    }"#;
+
+    #[test]
+    fn imports_int_grid_and_entities() {
+        let mut world = PhysicsWorld::new();
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        let project = load_ldtk_json(PROJECT, &mut world, &mut bodies, &mut colliders, |id| {
+            id == "Door"
+        })
+        .unwrap();
+
+        let level = &project.levels[0];
+        assert_eq!(level.identifier, "Level_0");
+        let (identifier, tilemap) = &level.tilemaps[0];
+        assert_eq!(identifier, "Collisions");
+        assert_eq!(tilemap.rects().count(), 2);
+        assert_eq!(bodies[tilemap.body()].position.x(), to_fp(256));
+
+        let door = &colliders[project.entities["e-door"]];
+        assert!(door.user_tag);
+        assert!(matches!(door.state, ColliderState::Sensor));
+        let position = bodies[door.owner].position;
+        assert_eq!((position.x(), position.y()), (to_fp(256 + 16), to_fp(8)));
+    }
+
+    #[test]
+    fn rejects_external_levels() {
+        let json = r#"{ "levels": [{ "identifier": "Level_0", "layerInstances": null }] }"#;
+        let result = load_ldtk_json(
+            json,
+            &mut PhysicsWorld::new(),
+            &mut BodySet::new(),
+            &mut ColliderSet::new(),
+            |_| (),
+        );
+        assert!(matches!(result, Err(LdtkError::ExternalLevel(_))));
+    }
+}
@@ -0,0 +1,11 @@
+//! Importers populating the world from level editor files, each enabled by its own feature.
+
+#[cfg(feature = "ldtk")]
+mod ldtk;
+#[cfg(feature = "tiled")]
+mod tiled;
+
+#[cfg(feature = "ldtk")]
+pub use self::ldtk::{load_ldtk_json, LdtkError, LdtkLevel, LdtkProject};
+#[cfg(feature = "tiled")]
+pub use self::tiled::{load_tiled_json, TiledError, TiledMap, TiledObject};
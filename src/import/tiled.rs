@@ -0,0 +1,517 @@
+use crate::builder::{BodyDesc, ColliderDesc};
+use crate::{
+    BodyHandle, BodySet, ColliderHandle, ColliderSet, PhysicsWorld, Tilemap, TilemapDesc, Vec2,
+    AABB,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fmt;
+
+// the highest four bits of a gid store flipping flags, the lowest of them the hexagonal 120° rotation
+const GID_MASK: u32 = 0x0FFF_FFFF;
+
+/// Error generated while importing a Tiled map.
+#[derive(Debug)]
+pub enum TiledError {
+    /// The file isn't valid Tiled JSON
+    Json(serde_json::Error),
+    /// Layer's tile data uses an encoding other than the plain array (`base64`, compression)
+    UnsupportedEncoding(String),
+    /// Layer's tile data is split into chunks (infinite maps)
+    InfiniteLayer(String),
+    /// Tileset is stored in a separate file, only embedded tilesets are supported
+    ExternalTileset(String),
+    /// Layer's tile data doesn't match its dimensions
+    InvalidTileData(String),
+}
+
+impl fmt::Display for TiledError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TiledError::Json(error) => write!(f, "invalid Tiled JSON: {}", error),
+            TiledError::UnsupportedEncoding(layer) => {
+                write!(
+                    f,
+                    "layer {:?} doesn't store its tiles as a plain array",
+                    layer
+                )
+            }
+            TiledError::InfiniteLayer(layer) => {
+                write!(f, "layer {:?} belongs to an infinite map", layer)
+            }
+            TiledError::ExternalTileset(source) => {
+                write!(f, "tileset {:?} isn't embedded in the map", source)
+            }
+            TiledError::InvalidTileData(layer) => {
+                write!(f, "tile layer {:?} doesn't match its dimensions", layer)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TiledError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TiledError::Json(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for TiledError {
+    fn from(error: serde_json::Error) -> Self {
+        TiledError::Json(error)
+    }
+}
+
+/// Collider created from a rectangle of an object layer.
+#[derive(Debug, Clone)]
+pub struct TiledObject {
+    /// Id assigned by Tiled, unique within the map
+    pub id: u32,
+    pub name: String,
+    /// Name of the object layer the object was placed on
+    pub layer: String,
+    pub body: BodyHandle,
+    pub collider: ColliderHandle,
+}
+
+/// Result of importing a Tiled map.
+#[derive(Debug, Clone)]
+pub struct TiledMap<T> {
+    /// One tilemap per tile layer containing at least one colliding tile, paired with the layer's name
+    pub tilemaps: Vec<(String, Tilemap<T>)>,
+    pub objects: Vec<TiledObject>,
+}
+
+/// Populates the world with colliders described by a Tiled JSON map.
+///
+/// Tile layers become `Tilemap`s, a tile is solid when its tileset entry has a `collides` property set to `true`
+/// or when the layer itself has it set. Rectangles of object layers become static bodies with a single collider,
+/// other kinds of objects (points, ellipses, polygons, rotated rectangles) are skipped.
+///
+/// Custom properties of layers and objects (objects override their layer) are mapped to the colliders:
+/// - `sensor` (bool) makes the collider a sensor
+/// - `category` (int) sets `category_bits`
+/// - `mask` (int) sets `mask_bits`
+/// - `overlap` (int) sets `overlap_bits`
+/// - `tag` (string) is passed to `user_tag` which turns it into the collider's tag
+pub fn load_tiled_json<T: Copy>(
+    json: &str,
+    world: &mut PhysicsWorld<T>,
+    bodies: &mut BodySet,
+    colliders: &mut ColliderSet<T>,
+    mut user_tag: impl FnMut(Option<&str>) -> T,
+) -> Result<TiledMap<T>, TiledError> {
+    let map: Map = serde_json::from_str(json)?;
+    if let Some(tileset) = map.tilesets.iter().find(|tileset| tileset.source.is_some()) {
+        return Err(TiledError::ExternalTileset(
+            tileset.source.clone().unwrap_or_default(),
+        ));
+    }
+
+    let mut layers = Vec::new();
+    flatten_layers(&map.layers, Vec2::zero(), &mut layers);
+
+    let mut result = TiledMap {
+        tilemaps: Vec::new(),
+        objects: Vec::new(),
+    };
+    let tile_size = Vec2::from(map.tilewidth, map.tileheight);
+
+    for (layer, layer_offset) in layers {
+        let properties = Properties::new(&layer.properties, None);
+        match layer.kind.as_str() {
+            "tilelayer" => {
+                if layer.chunks.is_some() {
+                    return Err(TiledError::InfiniteLayer(layer.name.clone()));
+                }
+                let data = match &layer.data {
+                    LayerData::Tiles(data)
+                        if layer.encoding.as_deref().unwrap_or("csv") == "csv"
+                            && layer.compression.as_deref().unwrap_or("").is_empty() =>
+                    {
+                        data
+                    }
+                    _ => return Err(TiledError::UnsupportedEncoding(layer.name.clone())),
+                };
+                if data.len() != layer.width * layer.height {
+                    return Err(TiledError::InvalidTileData(layer.name.clone()));
+                }
+                let layer_collides = properties.bool("collides").unwrap_or(false);
+                let cells: Vec<bool> = data
+                    .iter()
+                    .map(|&gid| {
+                        let gid = gid & GID_MASK;
+                        gid != 0 && (layer_collides || map.tile_collides(gid))
+                    })
+                    .collect();
+                if !cells.contains(&true) {
+                    continue;
+                }
+
+                let collider = properties.collider(ColliderDesc::new(
+                    AABB::default(),
+                    user_tag(properties.string("tag")),
+                ));
+                let tilemap =
+                    TilemapDesc::new(layer.width, layer.height, tile_size, collider.user_tag)
+                        .with_position(layer_offset + Vec2::from(layer.x, layer.y) * tile_size)
+                        .with_cells(cells)
+                        .with_collider(collider)
+                        .build(world, bodies, colliders);
+                result.tilemaps.push((layer.name.clone(), tilemap));
+            }
+            "objectgroup" => {
+                for object in layer.objects.iter() {
+                    if object.point
+                        || object.ellipse
+                        || object.polygon.is_some()
+                        || object.polyline.is_some()
+                        || object.gid.is_some()
+                        || object.rotation != 0.
+                        || object.width <= 0.
+                        || object.height <= 0.
+                    {
+                        log::debug!(
+                            "Tiled import: skipping object {} ({:?}), only rectangles are supported",
+                            object.id,
+                            object.name
+                        );
+                        continue;
+                    }
+                    let properties = Properties::new(&object.properties, Some(&layer.properties));
+                    let half_exts = Vec2::from(object.width, object.height).mul_scalar(0.5);
+                    let body = bodies.insert(
+                        BodyDesc::new()
+                            .with_position(
+                                layer_offset + Vec2::from(object.x, object.y) + half_exts,
+                            )
+                            .make_static()
+                            .build(),
+                    );
+                    let collider = properties
+                        .collider(ColliderDesc::new(
+                            AABB { half_exts },
+                            user_tag(properties.string("tag")),
+                        ))
+                        .build(body);
+                    let collider = colliders
+                        .insert(collider, bodies, world)
+                        .expect("Tiled import: freshly inserted body is missing");
+                    result.objects.push(TiledObject {
+                        id: object.id,
+                        name: object.name.clone(),
+                        layer: layer.name.clone(),
+                        body,
+                        collider,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(result)
+}
+
+// group layers are flattened with their offsets accumulated
+fn flatten_layers<'a>(layers: &'a [Layer], offset: Vec2, out: &mut Vec<(&'a Layer, Vec2)>) {
+    for layer in layers {
+        let offset = offset + Vec2::from(layer.offsetx, layer.offsety);
+        if layer.kind == "group" {
+            flatten_layers(&layer.layers, offset, out);
+        } else {
+            out.push((layer, offset));
+        }
+    }
+}
+
+struct Properties<'a> {
+    own: &'a [Property],
+    inherited: Option<&'a [Property]>,
+}
+
+impl<'a> Properties<'a> {
+    fn new(own: &'a [Property], inherited: Option<&'a [Property]>) -> Self {
+        Self { own, inherited }
+    }
+    fn get(&self, name: &str) -> Option<&'a Value> {
+        self.own
+            .iter()
+            .chain(self.inherited.unwrap_or(&[]).iter())
+            .find(|property| property.name == name)
+            .map(|property| &property.value)
+    }
+    fn bool(&self, name: &str) -> Option<bool> {
+        self.get(name)?.as_bool()
+    }
+    fn u32(&self, name: &str) -> Option<u32> {
+        let value = self.get(name)?;
+        // Tiled stores ints as JSON numbers, but bitmasks are commonly typed in as strings
+        value
+            .as_u64()
+            .or_else(|| value.as_i64().map(|value| value as u64))
+            .or_else(|| parse_bits(value.as_str()?))
+            .map(|value| value as u32)
+    }
+    fn string(&self, name: &str) -> Option<&'a str> {
+        self.get(name)?.as_str()
+    }
+    fn collider<T: Copy>(&self, mut desc: ColliderDesc<T>) -> ColliderDesc<T> {
+        if self.bool("sensor").unwrap_or(false) {
+            desc = desc.sensor();
+        }
+        if let Some(category_bits) = self.u32("category") {
+            desc = desc.with_category(category_bits);
+        }
+        if let Some(mask_bits) = self.u32("mask") {
+            desc = desc.with_mask(mask_bits);
+        }
+        if let Some(overlap_bits) = self.u32("overlap") {
+            desc = desc.with_overlap_mask(overlap_bits);
+        }
+        desc
+    }
+}
+
+fn parse_bits(text: &str) -> Option<u64> {
+    let text = text.trim();
+    if let Some(hex) = text.strip_prefix("0x") {
+        u64::from_str_radix(hex, 16).ok()
+    } else if let Some(binary) = text.strip_prefix("0b") {
+        u64::from_str_radix(binary, 2).ok()
+    } else {
+        text.parse().ok()
+    }
+}
+
+#[derive(Deserialize)]
+struct Map {
+    tilewidth: f64,
+    tileheight: f64,
+    #[serde(default)]
+    layers: Vec<Layer>,
+    #[serde(default)]
+    tilesets: Vec<Tileset>,
+}
+
+impl Map {
+    fn tile_collides(&self, gid: u32) -> bool {
+        let tileset = self
+            .tilesets
+            .iter()
+            .filter(|tileset| tileset.firstgid <= gid)
+            .max_by_key(|tileset| tileset.firstgid);
+        tileset
+            .and_then(|tileset| {
+                let id = gid - tileset.firstgid;
+                tileset.tiles.iter().find(|tile| tile.id == id)
+            })
+            .and_then(|tile| Properties::new(&tile.properties, None).bool("collides"))
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Deserialize)]
+struct Layer {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    x: f64,
+    #[serde(default)]
+    y: f64,
+    #[serde(default)]
+    offsetx: f64,
+    #[serde(default)]
+    offsety: f64,
+    #[serde(default)]
+    width: usize,
+    #[serde(default)]
+    height: usize,
+    #[serde(default)]
+    data: LayerData,
+    encoding: Option<String>,
+    compression: Option<String>,
+    chunks: Option<Value>,
+    #[serde(default)]
+    objects: Vec<Object>,
+    #[serde(default)]
+    layers: Vec<Layer>,
+    #[serde(default)]
+    properties: Vec<Property>,
+}
+
+// base64 data is stored as a string, it's only deserialized to report it as unsupported
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LayerData {
+    Tiles(Vec<u32>),
+    Encoded(#[allow(dead_code)] String),
+}
+
+impl Default for LayerData {
+    fn default() -> Self {
+        LayerData::Tiles(Vec::new())
+    }
+}
+
+#[derive(Deserialize)]
+struct Object {
+    id: u32,
+    #[serde(default)]
+    name: String,
+    x: f64,
+    y: f64,
+    #[serde(default)]
+    width: f64,
+    #[serde(default)]
+    height: f64,
+    #[serde(default)]
+    rotation: f64,
+    #[serde(default)]
+    point: bool,
+    #[serde(default)]
+    ellipse: bool,
+    polygon: Option<Value>,
+    polyline: Option<Value>,
+    gid: Option<u32>,
+    #[serde(default)]
+    properties: Vec<Property>,
+}
+
+#[derive(Deserialize)]
+struct Tileset {
+    #[serde(default)]
+    firstgid: u32,
+    source: Option<String>,
+    #[serde(default)]
+    tiles: Vec<Tile>,
+}
+
+#[derive(Deserialize)]
+struct Tile {
+    id: u32,
+    #[serde(default)]
+    properties: Vec<Property>,
+}
+
+#[derive(Deserialize)]
+struct Property {
+    name: String,
+    value: Value,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = r#"{
+        "tilewidth": 16, "tileheight": 16, "width": 3, "height": 2,
+        "tilesets": [{
+            "firstgid": 1,
+            "tiles": [{ "id": 0, "properties": [{ "name": "collides", "type": "bool", "value": true }] }]
+        }],
+        "layers": [
+            { "type": "tilelayer", "name": "ground", "width": 3, "height": 2, "x": 0, "y": 0,
+              "data": [1, 1, 2, 1, 268435457, 0] },
+            { "type": "objectgroup", "name": "triggers",
+              "properties": [{ "name": "sensor", "type": "bool", "value": true }],
+              "objects": [
+                { "id": 7, "name": "water", "x": 48, "y": 0, "width": 32, "height": 16, "rotation": 0,
+                  "properties": [
+                    { "name": "mask", "type": "string", "value": "0x4" },
+                    { "name": "tag", "type": "string", "value": "water" }
+                  ] },
+                { "id": 8, "name": "spawn", "x": 8, "y": 8, "point": true }
+              ] }
+        ]
+    }"#;
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    enum Tag {
+        Wall,
+        Water,
+    }
+
+    #[test]
+    fn imports_layers_and_objects() {
+        let mut world = PhysicsWorld::new();
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        let map = load_tiled_json(
+            MAP,
+            &mut world,
+            &mut bodies,
+            &mut colliders,
+            |tag| match tag {
+                Some("water") => Tag::Water,
+                _ => Tag::Wall,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(map.tilemaps.len(), 1);
+        let (name, tilemap) = &map.tilemaps[0];
+        assert_eq!(name, "ground");
+        assert!(tilemap.is_solid(1, 1));
+        assert!(!tilemap.is_solid(2, 0));
+        assert_eq!(tilemap.rects().count(), 1);
+
+        assert_eq!(map.objects.len(), 1);
+        let object = &map.objects[0];
+        assert_eq!((object.id, object.name.as_str()), (7, "water"));
+        let collider = &colliders[object.collider];
+        assert!(matches!(collider.state, crate::ColliderState::Sensor));
+        assert_eq!(collider.mask_bits, 4);
+        assert_eq!(collider.user_tag, Tag::Water);
+        assert_eq!(bodies[object.body].position.x(), crate::to_fp(64));
+    }
+
+    #[test]
+    fn rejects_external_tilesets() {
+        let json = r#"{ "tilewidth": 16, "tileheight": 16, "layers": [],
+                        "tilesets": [{ "firstgid": 1, "source": "tiles.tsx" }] }"#;
+        let result = load_tiled_json(
+            json,
+            &mut PhysicsWorld::new(),
+            &mut BodySet::new(),
+            &mut ColliderSet::new(),
+            |_| (),
+        );
+        assert!(matches!(result, Err(TiledError::ExternalTileset(_))));
+    }
+
+    #[test]
+    fn rejects_tile_data_not_matching_the_layer() {
+        let json = r#"{ "tilewidth": 16, "tileheight": 16, "tilesets": [],
+                        "layers": [{ "type": "tilelayer", "name": "ground", "width": 3, "height": 2,
+                                     "x": 0, "y": 0, "properties": [{ "name": "collides", "type": "bool", "value": true }],
+                                     "data": [1, 1, 1, 1] }] }"#;
+        let result = load_tiled_json(
+            json,
+            &mut PhysicsWorld::new(),
+            &mut BodySet::new(),
+            &mut ColliderSet::new(),
+            |_| (),
+        );
+        assert!(matches!(result, Err(TiledError::InvalidTileData(layer)) if layer == "ground"));
+    }
+
+    #[test]
+    fn rejects_encoded_tile_data() {
+        let json = r#"{ "tilewidth": 16, "tileheight": 16, "tilesets": [],
+                        "layers": [{ "type": "tilelayer", "name": "ground", "width": 2, "height": 1,
+                                     "x": 0, "y": 0, "encoding": "base64", "data": "AQAAAAEAAAA=" }] }"#;
+        let result = load_tiled_json(
+            json,
+            &mut PhysicsWorld::new(),
+            &mut BodySet::new(),
+            &mut ColliderSet::new(),
+            |_| (),
+        );
+        assert!(matches!(result, Err(TiledError::UnsupportedEncoding(layer)) if layer == "ground"));
+    }
+}
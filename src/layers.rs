@@ -0,0 +1,300 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+
+/// Named collision layer registered in a `LayerMatrix`, at most 32 of them.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Layer(u8);
+
+impl Layer {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+    /// Category bit of the layer, what `ColliderDesc::with_layer` sets as `category_bits`.
+    pub fn bit(self) -> u32 {
+        1 << self.0
+    }
+}
+
+/// How colliders on two layers treat each other.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LayerInteraction {
+    /// No resolution and no events.
+    Ignore,
+    /// Pass through each other, but send events as if one of them was a sensor.
+    Overlap,
+    /// Solid colliders block each other.
+    Collide,
+}
+
+/// Registry of named layers with a symmetric matrix of their interactions.
+/// Consulted whenever both colliders of a pair have a layer, otherwise their `category_bits`/`mask_bits` are used.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "LayerMatrixData"))]
+pub struct LayerMatrix {
+    names: Vec<String>,
+    // one row of layer bits per layer
+    collide: Vec<u32>,
+    overlap: Vec<u32>,
+}
+
+// deserialized before being checked, so the rows always match the layers
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct LayerMatrixData {
+    names: Vec<String>,
+    collide: Vec<u32>,
+    overlap: Vec<u32>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<LayerMatrixData> for LayerMatrix {
+    type Error = String;
+
+    fn try_from(data: LayerMatrixData) -> Result<Self, Self::Error> {
+        if data.names.len() > 32 {
+            return Err(format!(
+                "{} layers, at most 32 are supported",
+                data.names.len()
+            ));
+        }
+        if data.collide.len() != data.names.len() || data.overlap.len() != data.names.len() {
+            return Err(format!(
+                "{} layers, but {} collide and {} overlap rows",
+                data.names.len(),
+                data.collide.len(),
+                data.overlap.len()
+            ));
+        }
+        Ok(Self {
+            names: data.names,
+            collide: data.collide,
+            overlap: data.overlap,
+        })
+    }
+}
+
+impl LayerMatrix {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Registers a layer colliding with every layer, itself included.
+    /// Returns the existing layer if the name is taken, `None` if all 32 layers are in use.
+    pub fn add_layer(&mut self, name: &str) -> Option<Layer> {
+        if let Some(layer) = self.layer(name) {
+            return Some(layer);
+        }
+        if self.names.len() == 32 {
+            return None;
+        }
+        let layer = Layer(self.names.len() as u8);
+        self.names.push(name.to_owned());
+        self.collide.push(0);
+        self.overlap.push(0);
+        for other in 0..self.names.len() {
+            self.set_interaction(layer, Layer(other as u8), LayerInteraction::Collide);
+        }
+        Some(layer)
+    }
+    pub fn layer(&self, name: &str) -> Option<Layer> {
+        self.names
+            .iter()
+            .position(|layer_name| layer_name == name)
+            .map(|index| Layer(index as u8))
+    }
+    /// Panics if the layer wasn't registered in this matrix.
+    pub fn name(&self, layer: Layer) -> &str {
+        &self.names[layer.index()]
+    }
+    pub fn layers(&self) -> impl Iterator<Item = (Layer, &str)> {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (Layer(index as u8), name.as_str()))
+    }
+    /// Sets the interaction both ways, nothing changes for layers that aren't registered.
+    pub fn set_interaction(&mut self, a: Layer, b: Layer, interaction: LayerInteraction) {
+        for (row, column) in [(a, b), (b, a)] {
+            let (collide, overlap) = match interaction {
+                LayerInteraction::Ignore => (false, false),
+                LayerInteraction::Overlap => (false, true),
+                LayerInteraction::Collide => (true, false),
+            };
+            if let Some(bits) = self.collide.get_mut(row.index()) {
+                set_bit(bits, column, collide);
+            }
+            if let Some(bits) = self.overlap.get_mut(row.index()) {
+                set_bit(bits, column, overlap);
+            }
+        }
+    }
+    /// `Ignore` for layers that aren't registered.
+    pub fn interaction(&self, a: Layer, b: Layer) -> LayerInteraction {
+        let row = |rows: &[u32]| rows.get(a.index()).copied().unwrap_or(0);
+        if row(&self.collide) & b.bit() != 0 {
+            LayerInteraction::Collide
+        } else if row(&self.overlap) & b.bit() != 0 {
+            LayerInteraction::Overlap
+        } else {
+            LayerInteraction::Ignore
+        }
+    }
+    /// Bits of every layer interacting with `layer`, usable as the `collision_mask` of queries.
+    pub fn mask(&self, layer: Layer) -> u32 {
+        let row = |rows: &[u32]| rows.get(layer.index()).copied().unwrap_or(0);
+        row(&self.collide) | row(&self.overlap)
+    }
+}
+
+fn set_bit(bits: &mut u32, layer: Layer, value: bool) {
+    if value {
+        *bits |= layer.bit();
+    } else {
+        *bits &= !layer.bit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{BodyDesc, ColliderDesc};
+    use crate::{to_fp, BodySet, ColliderSet, ContactEvent, PhysicsWorld, Vec2, AABB};
+
+    #[test]
+    fn registers_layers_and_interactions() {
+        let mut layers = LayerMatrix::new();
+        let player = layers.add_layer("player").unwrap();
+        let enemy = layers.add_layer("enemy").unwrap();
+        let pickup = layers.add_layer("pickup").unwrap();
+        assert_eq!(layers.add_layer("enemy"), Some(enemy));
+        assert_eq!(layers.layer("pickup"), Some(pickup));
+        assert_eq!(layers.name(player), "player");
+        assert_eq!(layers.interaction(enemy, player), LayerInteraction::Collide);
+
+        layers.set_interaction(player, pickup, LayerInteraction::Overlap);
+        layers.set_interaction(enemy, pickup, LayerInteraction::Ignore);
+        assert_eq!(
+            layers.interaction(pickup, player),
+            LayerInteraction::Overlap
+        );
+        assert_eq!(layers.interaction(pickup, enemy), LayerInteraction::Ignore);
+        assert_eq!(layers.mask(pickup), player.bit() | pickup.bit());
+
+        for index in 3..32 {
+            assert!(layers.add_layer(&index.to_string()).is_some());
+        }
+        assert!(layers.add_layer("one too many").is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn round_trips_through_json() {
+        let mut layers = LayerMatrix::new();
+        let player = layers.add_layer("player").unwrap();
+        let pickup = layers.add_layer("pickup").unwrap();
+        layers.set_interaction(player, pickup, LayerInteraction::Overlap);
+        let json = serde_json::to_string(&layers).unwrap();
+        assert_eq!(serde_json::from_str::<LayerMatrix>(&json).unwrap(), layers);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn rejects_malformed_matrices() {
+        let missing_rows = r#"{ "names": ["player", "pickup"], "collide": [3], "overlap": [] }"#;
+        assert!(serde_json::from_str::<LayerMatrix>(missing_rows).is_err());
+        let names: Vec<_> = (0..33).map(|index| index.to_string()).collect();
+        let too_many = serde_json::json!({
+            "names": names,
+            "collide": vec![0; 33],
+            "overlap": vec![0; 33],
+        });
+        assert!(serde_json::from_value::<LayerMatrix>(too_many).is_err());
+    }
+
+    #[test]
+    fn matrix_drives_the_solver_and_queries() {
+        let mut world = PhysicsWorld::<&str>::new();
+        let player = world.layers.add_layer("player").unwrap();
+        let ground = world.layers.add_layer("ground").unwrap();
+        let pickup = world.layers.add_layer("pickup").unwrap();
+        world
+            .layers
+            .set_interaction(player, pickup, LayerInteraction::Overlap);
+        world
+            .layers
+            .set_interaction(ground, pickup, LayerInteraction::Ignore);
+
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+        let square = AABB {
+            half_exts: Vec2::from(8, 8),
+        };
+        let floor = bodies.insert(BodyDesc::new().make_static().build());
+        colliders.insert(
+            ColliderDesc::new(
+                AABB {
+                    half_exts: Vec2::from(100, 8),
+                },
+                "floor",
+            )
+            .with_layer(ground)
+            .build(floor),
+            &mut bodies,
+            &mut world,
+        );
+        let coin = bodies.insert(
+            BodyDesc::new()
+                .with_position(Vec2::from(0, -24))
+                .make_static()
+                .build(),
+        );
+        colliders.insert(
+            ColliderDesc::new(square, "coin")
+                .with_layer(pickup)
+                .build(coin),
+            &mut bodies,
+            &mut world,
+        );
+        let falling = bodies.insert(
+            BodyDesc::new()
+                .with_position(Vec2::from(0, -60))
+                .with_velocity(Vec2::from(0, 600))
+                .build(),
+        );
+        colliders.insert(
+            ColliderDesc::new(square, "player")
+                .with_layer(player)
+                .build(falling),
+            &mut bodies,
+            &mut world,
+        );
+
+        let mut overlapped = false;
+        for _ in 0..30 {
+            world.step(to_fp(1. / 60.), &mut bodies, &mut colliders);
+            overlapped |= world.events().iter().any(|event| {
+                matches!(
+                    event,
+                    ContactEvent::OverlapStarted(_, _, "player", "coin")
+                        | ContactEvent::OverlapStarted(_, _, "coin", "player")
+                )
+            });
+        }
+        // fell through the coin and landed on the floor
+        assert!(overlapped);
+        assert_eq!(bodies[falling].position.y(), to_fp(-16));
+
+        let hits = world.overlap_test(
+            Vec2::from(0, -16),
+            Vec2::from(50, 50),
+            world.layers.mask(ground),
+            &bodies,
+            &colliders,
+        );
+        assert_eq!(hits.count(), 2);
+    }
+}
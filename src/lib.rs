@@ -1,14 +1,30 @@
 mod collision;
+mod debug_draw;
+mod error;
 mod event;
+pub mod import;
+mod layers;
+pub mod math;
 mod object;
+mod simple_world;
 mod structs;
+mod svg;
+#[cfg(test)]
+mod test_utils;
+mod tilemap;
 mod type_defs;
 mod world;
 
 pub use self::collision::*;
-pub use self::event::ContactEvent;
+pub use self::debug_draw::*;
+pub use self::error::PhysicsError;
+pub use self::event::{BodyContactEvent, ContactEvent, SensorEvent};
+pub use self::layers::*;
 pub use self::object::*;
+pub use self::simple_world::World;
 pub use self::structs::*;
+pub use self::svg::*;
+pub use self::tilemap::*;
 pub use self::type_defs::*;
 pub use self::world::*;
 
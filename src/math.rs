@@ -0,0 +1,112 @@
+//! Deterministic math functions for any `Scalar`, angles are in radians.
+//!
+//! For fixed point types the trigonometric functions use the CORDIC algorithm with `I4F28` intermediates
+//! and `sqrt` is computed on the integer bits, so results are bit for bit identical on every platform.
+//! With `FP` (16 fractional bits) `sin`, `cos` and `atan2` are within `3 * FP::DELTA` (about `0.00005`)
+//! of the exact result and `sqrt` is exact up to truncation of the last bit.
+//! Floating point types forward to the standard library.
+
+use crate::{Scalar, Vec2};
+
+/// `π` in the given number type.
+pub fn pi<N: Scalar>() -> N {
+    N::from_f64(std::f64::consts::PI)
+}
+
+/// `π / 2` in the given number type.
+pub fn frac_pi_2<N: Scalar>() -> N {
+    N::from_f64(std::f64::consts::FRAC_PI_2)
+}
+
+/// `2π` in the given number type.
+pub fn tau<N: Scalar>() -> N {
+    N::from_f64(std::f64::consts::TAU)
+}
+
+pub fn to_radians<N: Scalar>(degrees: N) -> N {
+    degrees * pi::<N>() / N::from_i32(180)
+}
+
+pub fn to_degrees<N: Scalar>(radians: N) -> N {
+    radians * N::from_i32(180) / pi::<N>()
+}
+
+/// Any angle is accepted, it's wrapped into the `-π..π` range first.
+pub fn sin<N: Scalar>(angle: N) -> N {
+    angle.sin_cos().0
+}
+
+/// Any angle is accepted, it's wrapped into the `-π..π` range first.
+pub fn cos<N: Scalar>(angle: N) -> N {
+    angle.sin_cos().1
+}
+
+/// Cheaper than calling `sin` and `cos` separately.
+pub fn sin_cos<N: Scalar>(angle: N) -> (N, N) {
+    angle.sin_cos()
+}
+
+/// Angle between the x axis and the point (`x`, `y`) in the `-π..=π` range, `0` for the origin.
+pub fn atan2<N: Scalar>(y: N, x: N) -> N {
+    y.atan2(x)
+}
+
+/// Panics for negative values when using fixed point numbers.
+pub fn sqrt<N: Scalar>(value: N) -> N {
+    value.sqrt()
+}
+
+/// Rotates `point` by `angle` around `pivot`, positive angles rotate clockwise on screen (y axis points down).
+pub fn rotate_around<N: Scalar>(point: Vec2<N>, pivot: Vec2<N>, angle: N) -> Vec2<N> {
+    pivot + (point - pivot).rotated(angle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{to_fp, FP};
+
+    fn error(value: FP, expected: f64) -> f64 {
+        (value.to_num::<f64>() - expected).abs()
+    }
+
+    #[test]
+    fn trigonometry_matches_f64() {
+        let bound = 3. * FP::DELTA.to_num::<f64>();
+        let mut angle = to_fp(-10);
+        while angle < to_fp(10) {
+            let exact = angle.to_num::<f64>();
+            assert!(error(sin(angle), exact.sin()) <= bound, "sin({})", angle);
+            assert!(error(cos(angle), exact.cos()) <= bound, "cos({})", angle);
+            angle += to_fp(0.01);
+        }
+
+        for y in -20..=20 {
+            for x in -20..=20 {
+                let (y, x) = (to_fp(y) / 4, to_fp(x) / 4);
+                let exact = if y == 0 && x == 0 {
+                    0.
+                } else {
+                    y.to_num::<f64>().atan2(x.to_num())
+                };
+                assert!(error(atan2(y, x), exact) <= bound, "atan2({}, {})", y, x);
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt_matches_f64() {
+        for value in (0..10_000).map(|i| to_fp(i) / 7) {
+            assert!(error(sqrt(value), value.to_num::<f64>().sqrt()) <= FP::DELTA.to_num::<f64>());
+        }
+    }
+
+    #[test]
+    fn conversions_and_rotation() {
+        assert!(error(to_radians(to_fp(90)), std::f64::consts::FRAC_PI_2) < 0.0001);
+        assert!(error(to_degrees(pi::<FP>()), 180.) < 0.01);
+
+        let rotated = rotate_around(Vec2::<FP>::from(3, 1), Vec2::from(1, 1), frac_pi_2());
+        assert!(error(rotated.x(), 1.) < 0.0001 && error(rotated.y(), 3.) < 0.0001);
+    }
+}
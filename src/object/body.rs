@@ -1,37 +1,86 @@
 use super::collider_set::ColliderHandle;
-use crate::Vec2;
+use crate::{Scalar, Vec2, FP};
 
 /// Describes a body.
 ///  
 /// It functions as a container for colliders.
 #[derive(Clone, Debug)]
-pub struct Body {
-    pub position: Vec2,
+pub struct Body<N = FP> {
+    pub position: Vec2<N>,
     /// static body CAN have velocity - it just behaves as if it had infinite mass  
     /// (this might change with introduction of kinematic body that pushes other objects)  
     /// and doesn't collide with other static bodies
-    pub velocity: Vec2,
+    pub velocity: Vec2<N>,
     /// Type of body - `static` or `kinematic`
     pub status: BodyStatus,
     /// Whether colliders of the same body should collide
     pub self_collide: bool,
+    /// Whether the body falls asleep after standing still for `PhysicsWorld::sleep_steps` steps
+    pub can_sleep: bool,
     // cached list of colliders belonging to body
     pub(crate) colliders: Vec<ColliderHandle>,
     // the distance body will want to cover during the next step
-    pub(crate) movement: Vec2,
+    pub(crate) movement: Vec2<N>,
+    // position at the end of the last step, a difference means it was moved by the user
+    pub(crate) rest_position: Vec2<N>,
+    pub(crate) still_steps: u32,
+    pub(crate) sleeping: bool,
+    // disabled bodies keep their handle but are skipped by stepping, queries and events
+    pub(crate) enabled: bool,
 }
 
-impl Body {
-    pub fn new(position: Vec2, velocity: Vec2, status: BodyStatus, self_collide: bool) -> Self {
+impl<N: Scalar> Body<N> {
+    pub fn new(
+        position: Vec2<N>,
+        velocity: Vec2<N>,
+        status: BodyStatus,
+        self_collide: bool,
+    ) -> Self {
         Self {
             position,
             velocity,
             status,
             self_collide,
+            can_sleep: true,
             colliders: Vec::new(),
             movement: Vec2::zero(),
+            rest_position: position,
+            still_steps: 0,
+            sleeping: false,
+            enabled: true,
         }
     }
+    /// Changed with `PhysicsWorld::set_body_enabled`.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+    /// Sleeping bodies are skipped by the solver until something wakes them up.
+    pub fn is_sleeping(&self) -> bool {
+        self.sleeping
+    }
+    /// Changing the position or velocity also wakes the body up during the next step.
+    pub fn wake_up(&mut self) {
+        self.sleeping = false;
+        self.still_steps = 0;
+    }
+    // whether the user moved the body since the last step
+    pub(crate) fn was_moved(&self) -> bool {
+        self.position != self.rest_position
+    }
+    // counts the steps without any velocity, `sleep_steps` of 0 disables sleeping
+    pub(crate) fn update_sleep(&mut self, sleep_steps: u32) {
+        if self.velocity != Vec2::zero() || self.was_moved() || !self.can_sleep || sleep_steps == 0
+        {
+            self.wake_up();
+        } else if !self.sleeping {
+            self.still_steps += 1;
+            self.sleeping = self.still_steps >= sleep_steps;
+        }
+    }
+    // enabled kinematic body which isn't sleeping
+    pub(crate) fn is_awake(&self) -> bool {
+        matches!(self.status, BodyStatus::Kinematic) && !self.sleeping && self.enabled
+    }
 }
 /// Status of the body, determines how it's affected by other bodies.
 #[derive(Copy, Clone, Debug)]
@@ -1,4 +1,5 @@
 use super::Body;
+use crate::{PhysicsError, Scalar, FP};
 use generational_arena::Arena;
 use std::ops::{Index, IndexMut};
 
@@ -8,17 +9,17 @@ use std::ops::{Index, IndexMut};
 pub struct BodyHandle(generational_arena::Index);
 
 /// Container for bodies, removal is currently performed through `PhysicsWorld`, but access and modification is possible through this structure
-pub struct BodySet {
-    bodies: Arena<Body>,
+pub struct BodySet<N = FP> {
+    bodies: Arena<Body<N>>,
 }
 
-impl Default for BodySet {
+impl<N: Scalar> Default for BodySet<N> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl BodySet {
+impl<N: Scalar> BodySet<N> {
     pub fn new() -> Self {
         Self {
             bodies: Arena::with_capacity(16),
@@ -26,44 +27,55 @@ impl BodySet {
     }
 
     /// Inserts a new body into the world and returns it's unique handle.
-    pub fn insert(&mut self, body: Body) -> BodyHandle {
+    pub fn insert(&mut self, body: Body<N>) -> BodyHandle {
         let key = self.bodies.insert(body);
         BodyHandle(key)
     }
 
-    pub fn get(&self, handle: BodyHandle) -> Option<&Body> {
+    pub fn get(&self, handle: BodyHandle) -> Option<&Body<N>> {
         self.bodies.get(handle.0)
     }
-    pub fn get_mut(&mut self, handle: BodyHandle) -> Option<&mut Body> {
+    pub fn get_mut(&mut self, handle: BodyHandle) -> Option<&mut Body<N>> {
         self.bodies.get_mut(handle.0)
     }
-    pub fn iter(&self) -> impl Iterator<Item = (crate::BodyHandle, &Body)> {
+    pub fn try_get(&self, handle: BodyHandle) -> Result<&Body<N>, PhysicsError> {
+        self.get(handle).ok_or(PhysicsError::BodyNotFound(handle))
+    }
+    pub fn try_get_mut(&mut self, handle: BodyHandle) -> Result<&mut Body<N>, PhysicsError> {
+        self.get_mut(handle)
+            .ok_or(PhysicsError::BodyNotFound(handle))
+    }
+    pub fn contains(&self, handle: BodyHandle) -> bool {
+        self.bodies.contains(handle.0)
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (crate::BodyHandle, &Body<N>)> {
         self.bodies
             .iter()
             .map(|(index, body)| (BodyHandle(index), body))
     }
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = (crate::BodyHandle, &mut Body)> {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (crate::BodyHandle, &mut Body<N>)> {
         self.bodies
             .iter_mut()
             .map(|(index, body)| (BodyHandle(index), body))
     }
-    pub(crate) fn internal_remove(&mut self, handle: BodyHandle) -> Body {
+    pub(crate) fn internal_remove(&mut self, handle: BodyHandle) -> Body<N> {
         self.bodies
             .remove(handle.0)
             .expect("Tried to remove nonexistent body")
     }
 }
 
-impl Index<BodyHandle> for BodySet {
-    type Output = Body;
+/// Panics on stale handles, see `BodySet::try_get`.
+impl<N> Index<BodyHandle> for BodySet<N> {
+    type Output = Body<N>;
 
-    fn index(&self, index: BodyHandle) -> &Body {
+    fn index(&self, index: BodyHandle) -> &Body<N> {
         &self.bodies[index.0]
     }
 }
 
-impl IndexMut<BodyHandle> for BodySet {
-    fn index_mut(&mut self, index: BodyHandle) -> &mut Body {
+impl<N> IndexMut<BodyHandle> for BodySet<N> {
+    fn index_mut(&mut self, index: BodyHandle) -> &mut Body<N> {
         &mut self.bodies[index.0]
     }
 }
@@ -1,37 +1,39 @@
-pub use super::super::collision::AABB;
-pub use super::{Body, BodyHandle, BodyStatus, Collider, ColliderState};
-use crate::Vec2;
+pub use super::super::collision::{Shape, AABB};
+pub use super::{Body, BodyHandle, BodyStatus, Collider, ColliderEvents, ColliderState};
+use crate::{Layer, Scalar, Vec2, FP};
 
 /// Builder for the `Body`. Start with `new`, finish with `build`.
 #[derive(Debug, Clone)]
-pub struct BodyDesc {
-    pub position: Vec2,
+pub struct BodyDesc<N = FP> {
+    pub position: Vec2<N>,
 
-    pub velocity: Vec2,
+    pub velocity: Vec2<N>,
     pub status: BodyStatus,
     pub self_collide: bool,
+    pub can_sleep: bool,
 }
 
-impl Default for BodyDesc {
+impl<N: Scalar> Default for BodyDesc<N> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl BodyDesc {
+impl<N: Scalar> BodyDesc<N> {
     pub fn new() -> Self {
         Self {
             position: Vec2::zero(),
             velocity: Vec2::zero(),
             status: BodyStatus::Kinematic,
             self_collide: true,
+            can_sleep: true,
         }
     }
-    pub fn with_position(mut self, position: Vec2) -> Self {
+    pub fn with_position(mut self, position: Vec2<N>) -> Self {
         self.position = position;
         self
     }
-    pub fn with_velocity(mut self, velocity: Vec2) -> Self {
+    pub fn with_velocity(mut self, velocity: Vec2<N>) -> Self {
         self.velocity = velocity;
         self
     }
@@ -43,40 +45,54 @@ impl BodyDesc {
         self.self_collide = check;
         self
     }
-    pub fn build(self) -> Body {
-        Body::new(self.position, self.velocity, self.status, self.self_collide)
+    /// Bodies which are never allowed to fall asleep, e.g. the player.
+    pub fn can_sleep(mut self, can_sleep: bool) -> Self {
+        self.can_sleep = can_sleep;
+        self
+    }
+    pub fn build(self) -> Body<N> {
+        Body {
+            can_sleep: self.can_sleep,
+            ..Body::new(self.position, self.velocity, self.status, self.self_collide)
+        }
     }
 }
 
 // Builder for the `Collider`. Start with `new`, finish with `build`.
 #[derive(Debug, Clone)]
-pub struct ColliderDesc<T> {
-    pub shape: AABB,
-    pub offset: Vec2,
+pub struct ColliderDesc<T, N = FP> {
+    pub shape: Shape<N>,
+    pub offset: Vec2<N>,
     pub state: ColliderState,
 
     pub category_bits: u32,
     pub mask_bits: u32,
+    pub overlap_bits: u32,
+    pub layer: Option<Layer>,
+    pub events: ColliderEvents,
 
     pub user_tag: T,
 }
 
-impl<T: Copy> ColliderDesc<T> {
-    pub fn new(shape: AABB, user_tag: T) -> Self {
+impl<T: Copy, N: Scalar> ColliderDesc<T, N> {
+    pub fn new(shape: impl Into<Shape<N>>, user_tag: T) -> Self {
         Self {
-            shape,
+            shape: shape.into(),
             offset: Vec2::zero(),
             state: ColliderState::Solid,
             category_bits: 1,
             mask_bits: u32::MAX,
+            overlap_bits: 0,
+            layer: None,
+            events: ColliderEvents::All,
             user_tag,
         }
     }
-    pub fn with_shape(mut self, shape: AABB) -> Self {
-        self.shape = shape;
+    pub fn with_shape(mut self, shape: impl Into<Shape<N>>) -> Self {
+        self.shape = shape.into();
         self
     }
-    pub fn with_offset(mut self, offset: Vec2) -> Self {
+    pub fn with_offset(mut self, offset: Vec2<N>) -> Self {
         self.offset = offset;
         self
     }
@@ -92,19 +108,40 @@ impl<T: Copy> ColliderDesc<T> {
         self.mask_bits = mask_bits;
         self
     }
+    /// Categories the collider passes through while still sending overlap events, e.g. other players.
+    pub fn with_overlap_mask(mut self, overlap_bits: u32) -> Self {
+        self.overlap_bits = overlap_bits;
+        self
+    }
+    /// Puts the collider on a layer of the world's `LayerMatrix`, its `category_bits` become the layer's bit.
+    pub fn with_layer(mut self, layer: Layer) -> Self {
+        self.layer = Some(layer);
+        self.category_bits = layer.bit();
+        self
+    }
+    /// Opts out of the events nobody reads, e.g. `ColliderEvents::None` for static tiles.
+    pub fn with_events(mut self, events: ColliderEvents) -> Self {
+        self.events = events;
+        self
+    }
     pub fn with_tag(mut self, user_tag: T) -> Self {
         self.user_tag = user_tag;
         self
     }
-    pub fn build(self, owner: BodyHandle) -> Collider<T> {
-        Collider::new(
-            self.shape,
-            self.offset,
-            self.state,
-            self.category_bits,
-            self.mask_bits,
-            self.user_tag,
-            owner,
-        )
+    pub fn build(self, owner: BodyHandle) -> Collider<T, N> {
+        Collider {
+            overlap_bits: self.overlap_bits,
+            layer: self.layer,
+            events: self.events,
+            ..Collider::new(
+                self.shape,
+                self.offset,
+                self.state,
+                self.category_bits,
+                self.mask_bits,
+                self.user_tag,
+                owner,
+            )
+        }
     }
 }
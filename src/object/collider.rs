@@ -1,31 +1,41 @@
-use super::super::collision::{self, ContactManifold, AABB};
+use super::super::collision::{self, Contact, ContactManifold, Shape, AABB};
 use super::super::collision::{Ray, Raycast};
 use super::body_set::BodyHandle;
-use crate::{Vec2, FP};
+use crate::{ContactEvent, Layer, Scalar, Vec2, FP};
 
 /// Describes a collider in the shape of `Shape`. Attached to a body.
+/// Once inserted, change it through `PhysicsWorld::set_collider_*` to keep bodies and interactions in sync.
 #[derive(Clone, Debug)]
-pub struct Collider<T> {
-    /// Currently the only shape is `AABB`
-    pub shape: AABB,
+pub struct Collider<T, N = FP> {
+    /// Only `AABB` and `Capsule` shapes are resolved by the solver, against each other and against `SegmentChain`s.
+    /// The others just report collisions
+    pub shape: Shape<N>,
     /// Offset from the body's position, 0 for centered
-    pub offset: Vec2,
+    pub offset: Vec2<N>,
     /// Whether to treat the body as physical or not
     pub state: ColliderState,
     /// Ideally only one bit should be set
     pub category_bits: u32,
     /// Bodies only collide if both of their masks match
     pub mask_bits: u32,
+    /// Categories this collider only overlaps with even when both are solid, checked both ways
+    pub overlap_bits: u32,
+    /// When both colliders of a pair have a layer the world's `LayerMatrix` is used instead of the bits
+    pub layer: Option<Layer>,
+    /// Kinds of `ContactEvent`s sent for its interactions
+    pub events: ColliderEvents,
     /// User supplied tag for identification
     pub user_tag: T,
     /// Body who owns the collider
     pub owner: BodyHandle,
+    // disabled colliders keep their handle but are skipped by stepping, queries and events
+    pub(crate) enabled: bool,
 }
 
-impl<T> Collider<T> {
+impl<T, N: Scalar> Collider<T, N> {
     pub fn new(
-        shape: AABB,
-        offset: Vec2,
+        shape: Shape<N>,
+        offset: Vec2<N>,
         state: ColliderState,
         category_bits: u32,
         mask_bits: u32,
@@ -38,74 +48,136 @@ impl<T> Collider<T> {
             state,
             category_bits,
             mask_bits,
+            overlap_bits: 0,
+            layer: None,
+            events: ColliderEvents::All,
             user_tag,
             owner,
+            enabled: true,
         }
     }
-    pub fn overlaps_aabb(&self, own_position: Vec2, position: Vec2, half_exts: Vec2) -> bool {
+    /// Changed with `PhysicsWorld::set_collider_enabled`, also inactive while its body is disabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+    pub fn overlaps_aabb(
+        &self,
+        own_position: Vec2<N>,
+        position: Vec2<N>,
+        half_exts: Vec2<N>,
+    ) -> bool {
         let own_position = own_position + self.offset;
-        collision::intersection_aabb_aabb(own_position, self.shape.half_exts, position, half_exts)
+        self.shape
+            .intersects(own_position, &AABB { half_exts }.into(), position)
     }
-    pub fn ray_contact(&self, own_position: Vec2, ray: &Ray) -> Option<Raycast> {
+    pub fn ray_contact(&self, own_position: Vec2<N>, ray: &Ray<N>) -> Option<Raycast<N>> {
         let own_position = own_position + self.offset;
-        collision::contact_ray_aabb(ray, own_position, self.shape.half_exts)
+        self.shape.ray_contact(own_position, ray)
     }
 }
 
 /// Boolean test whether two `Colliders` collided.
-pub fn is_colliding<T>(
-    collider1: &Collider<T>,
-    position1: Vec2,
-    collider2: &Collider<T>,
-    position2: Vec2,
+pub fn is_colliding<T, N: Scalar>(
+    collider1: &Collider<T, N>,
+    position1: Vec2<N>,
+    collider2: &Collider<T, N>,
+    position2: Vec2<N>,
 ) -> bool {
     // apply offset
     let position1 = position1 + collider1.offset;
     let position2 = position2 + collider2.offset;
-    collision::intersection_aabb_aabb(
-        position1,
-        collider1.shape.half_exts,
-        position2,
-        collider2.shape.half_exts,
-    )
+    collider1
+        .shape
+        .intersects(position1, &collider2.shape, position2)
 }
 
-pub fn is_penetrating<T>(
-    collider1: &Collider<T>,
-    position1: Vec2,
-    collider2: &Collider<T>,
-    position2: Vec2,
-    tolerance: FP,
+/// Always false unless both shapes are either `AABB`s or `Capsule`s, the only ones resolved by the solver.
+pub fn is_penetrating<T, N: Scalar>(
+    collider1: &Collider<T, N>,
+    position1: Vec2<N>,
+    collider2: &Collider<T, N>,
+    position2: Vec2<N>,
+    tolerance: N,
 ) -> bool {
     let position1 = position1 + collider1.offset;
     let position2 = position2 + collider2.offset;
-    collision::intersection_aabb_aabb(
+    collider1
+        .shape
+        .penetrates(position1, &collider2.shape, position2, tolerance)
+}
+
+// Distance between the centers of two colliders resolved by the solver at which they touch on each axis.
+pub(crate) fn solver_reach<T, N: Scalar>(
+    collider1: &Collider<T, N>,
+    position1: Vec2<N>,
+    collider2: &Collider<T, N>,
+    position2: Vec2<N>,
+) -> Vec2<N> {
+    let position1 = position1 + collider1.offset;
+    let position2 = position2 + collider2.offset;
+    collider1
+        .shape
+        .reach(position1, &collider2.shape, position2)
+}
+
+// Signed distance along the unit `axis` collider1 can move before resting on collider2 when it's a `SegmentChain`.
+pub(crate) fn solver_stop<T, N: Scalar>(
+    collider1: &Collider<T, N>,
+    position1: Vec2<N>,
+    axis: Vec2<N>,
+    distance: N,
+    collider2: &Collider<T, N>,
+    position2: Vec2<N>,
+    tolerance: N,
+) -> Option<N> {
+    let Shape::Chain(chain) = &collider2.shape else {
+        return None;
+    };
+    let position1 = position1 + collider1.offset;
+    let position2 = position2 + collider2.offset;
+    collision::chain_stop(
+        &collider1.shape,
         position1,
-        collider1.shape.half_exts - Vec2::splat(tolerance),
+        axis,
+        distance,
         position2,
-        collider2.shape.half_exts,
+        chain,
+        tolerance,
     )
 }
 
 /// Generates a ContactManifold if two `Colliders` collided.
-pub fn collision_manifold<T>(
-    collider1: &Collider<T>,
-    position1: Vec2,
-    collider2: &Collider<T>,
-    position2: Vec2,
-) -> Option<ContactManifold> {
+/// Only `AABB` pairs have a manifold, use `collision_contact` for the other shapes.
+pub fn collision_manifold<T, N: Scalar>(
+    collider1: &Collider<T, N>,
+    position1: Vec2<N>,
+    collider2: &Collider<T, N>,
+    position2: Vec2<N>,
+) -> Option<ContactManifold<N>> {
+    let (aabb1, aabb2) = (collider1.shape.as_aabb()?, collider2.shape.as_aabb()?);
     // apply offset
     let position1 = position1 + collider1.offset;
     let position2 = position2 + collider2.offset;
-    collision::contact_aabb_aabb(
-        position1,
-        collider1.shape.half_exts,
-        position2,
-        collider2.shape.half_exts,
-    )
+    collision::contact_aabb_aabb(position1, aabb1.half_exts, position2, aabb2.half_exts)
+}
+
+/// Generates the deepest contact if two `Colliders` collided, works for any pair of shapes.
+pub fn collision_contact<T, N: Scalar>(
+    collider1: &Collider<T, N>,
+    position1: Vec2<N>,
+    collider2: &Collider<T, N>,
+    position2: Vec2<N>,
+) -> Option<Contact<N>> {
+    // apply offset
+    let position1 = position1 + collider1.offset;
+    let position2 = position2 + collider2.offset;
+    collider1
+        .shape
+        .contact(position1, &collider2.shape, position2)
 }
 
 /// State of the collider, determines default collision resolution and types of events sent.
+/// A pair of solid colliders can still only overlap because of `overlap_bits` or the world's `LayerMatrix`.
 #[derive(Copy, Clone, Debug)]
 pub enum ColliderState {
     /// Solid body resolves collision.
@@ -113,3 +185,27 @@ pub enum ColliderState {
     /// Sensor sends events about possible overlap.
     Sensor,
 }
+
+/// Kinds of `ContactEvent`s sent for the interactions of a collider, a pair only sends the ones both colliders want.  
+/// Interactions are tracked regardless, e.g. for `PhysicsWorld::interactions_of`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ColliderEvents {
+    #[default]
+    All,
+    Collisions,
+    Overlaps,
+    None,
+}
+
+impl ColliderEvents {
+    pub fn allows<T>(self, event: &ContactEvent<T>) -> bool {
+        match event {
+            ContactEvent::CollisionStarted(..) | ContactEvent::CollisionEnded(..) => {
+                matches!(self, Self::All | Self::Collisions)
+            }
+            ContactEvent::OverlapStarted(..) | ContactEvent::OverlapEnded(..) => {
+                matches!(self, Self::All | Self::Overlaps)
+            }
+        }
+    }
+}
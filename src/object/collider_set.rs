@@ -1,4 +1,6 @@
-use super::{BodySet, Collider};
+use super::{BodySet, BodyStatus, Collider};
+use crate::collision::Shape;
+use crate::{PhysicsError, Scalar, FP};
 use generational_arena::Arena;
 use std::ops::{Index, IndexMut};
 
@@ -8,17 +10,17 @@ use std::ops::{Index, IndexMut};
 pub struct ColliderHandle(generational_arena::Index);
 
 /// Container for colliders, removal is currently performed through `PhysicsWorld`, but access and modification is possible through this structure
-pub struct ColliderSet<T> {
-    colliders: Arena<Collider<T>>,
+pub struct ColliderSet<T, N = FP> {
+    colliders: Arena<Collider<T, N>>,
 }
 
-impl<T> Default for ColliderSet<T> {
+impl<T, N: Scalar> Default for ColliderSet<T, N> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<T> ColliderSet<T> {
+impl<T, N: Scalar> ColliderSet<T, N> {
     pub fn new() -> Self {
         Self {
             colliders: Arena::with_capacity(128),
@@ -27,53 +29,87 @@ impl<T> ColliderSet<T> {
 
     /// Inserts a new collider into the Set if it's associated body exists.  
     /// In the case where body doesn't exist returns `None`.  
+    /// Panics if a `SegmentChain` is attached to a body that isn't static, see `try_insert`.  
     /// Currently requires `PhysicsWorld` as an argument to add a node to `CollisionGraph`.
     pub fn insert(
         &mut self,
-        collider: Collider<T>,
-        bodies: &mut BodySet,
-        world: &mut crate::PhysicsWorld<T>,
+        collider: Collider<T, N>,
+        bodies: &mut BodySet<N>,
+        world: &mut crate::PhysicsWorld<T, N>,
     ) -> Option<ColliderHandle> {
-        let body = bodies.get_mut(collider.owner)?;
+        match self.try_insert(collider, bodies, world) {
+            Ok(handle) => Some(handle),
+            Err(error @ PhysicsError::ChainOnKinematicBody(_)) => panic!("{}", error),
+            Err(_) => None,
+        }
+    }
+    /// Same as `insert`, but tells why the collider couldn't be inserted.
+    pub fn try_insert(
+        &mut self,
+        collider: Collider<T, N>,
+        bodies: &mut BodySet<N>,
+        world: &mut crate::PhysicsWorld<T, N>,
+    ) -> Result<ColliderHandle, PhysicsError> {
+        let body = bodies.try_get_mut(collider.owner)?;
+        if let (Shape::Chain(_), BodyStatus::Kinematic) = (&collider.shape, body.status) {
+            return Err(PhysicsError::ChainOnKinematicBody(collider.owner));
+        }
         let key = self.colliders.insert(collider);
         world.collision_graph.add_node(ColliderHandle(key));
         body.colliders.push(ColliderHandle(key));
-        Some(ColliderHandle(key))
+        Ok(ColliderHandle(key))
     }
 
-    pub fn get(&self, handle: ColliderHandle) -> Option<&Collider<T>> {
+    pub fn get(&self, handle: ColliderHandle) -> Option<&Collider<T, N>> {
         self.colliders.get(handle.0)
     }
-    pub fn get_mut(&mut self, handle: ColliderHandle) -> Option<&mut Collider<T>> {
+    pub fn get_mut(&mut self, handle: ColliderHandle) -> Option<&mut Collider<T, N>> {
         self.colliders.get_mut(handle.0)
     }
-    pub fn iter(&self) -> impl Iterator<Item = (crate::ColliderHandle, &Collider<T>)> {
+    pub fn try_get(&self, handle: ColliderHandle) -> Result<&Collider<T, N>, PhysicsError> {
+        self.get(handle)
+            .ok_or(PhysicsError::ColliderNotFound(handle))
+    }
+    pub fn try_get_mut(
+        &mut self,
+        handle: ColliderHandle,
+    ) -> Result<&mut Collider<T, N>, PhysicsError> {
+        self.get_mut(handle)
+            .ok_or(PhysicsError::ColliderNotFound(handle))
+    }
+    pub fn contains(&self, handle: ColliderHandle) -> bool {
+        self.colliders.contains(handle.0)
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (crate::ColliderHandle, &Collider<T, N>)> {
         self.colliders
             .iter()
             .map(|(index, collider)| (ColliderHandle(index), collider))
     }
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = (crate::ColliderHandle, &mut Collider<T>)> {
+    pub fn iter_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (crate::ColliderHandle, &mut Collider<T, N>)> {
         self.colliders
             .iter_mut()
             .map(|(index, collider)| (ColliderHandle(index), collider))
     }
-    pub(crate) fn internal_remove(&mut self, handle: ColliderHandle) -> Collider<T> {
+    pub(crate) fn internal_remove(&mut self, handle: ColliderHandle) -> Collider<T, N> {
         self.colliders
             .remove(handle.0)
             .expect("Tried to remove nonexistent collider")
     }
 }
 
-impl<T> Index<ColliderHandle> for ColliderSet<T> {
-    type Output = Collider<T>;
+/// Panics on stale handles, see `ColliderSet::try_get`.
+impl<T, N> Index<ColliderHandle> for ColliderSet<T, N> {
+    type Output = Collider<T, N>;
 
-    fn index(&self, index: ColliderHandle) -> &Collider<T> {
+    fn index(&self, index: ColliderHandle) -> &Collider<T, N> {
         &self.colliders[index.0]
     }
 }
 
-impl<T> IndexMut<ColliderHandle> for ColliderSet<T> {
-    fn index_mut(&mut self, index: ColliderHandle) -> &mut Collider<T> {
+impl<T, N> IndexMut<ColliderHandle> for ColliderSet<T, N> {
+    fn index_mut(&mut self, index: ColliderHandle) -> &mut Collider<T, N> {
         &mut self.colliders[index.0]
     }
 }
@@ -7,6 +7,8 @@ mod collider_set;
 pub use self::body::{Body, BodyStatus};
 pub use self::body_set::{BodyHandle, BodySet};
 pub use self::collider::{
-    collision_manifold, is_colliding, is_penetrating, Collider, ColliderState,
+    collision_contact, collision_manifold, is_colliding, is_penetrating, Collider, ColliderEvents,
+    ColliderState,
 };
+pub(crate) use self::collider::{solver_reach, solver_stop};
 pub use self::collider_set::{ColliderHandle, ColliderSet};
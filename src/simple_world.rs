@@ -0,0 +1,257 @@
+use super::collision::{Interaction, Ray, Raycast, Shape};
+use super::event::{BodyContactEvent, ContactEvent, SensorEvent};
+use super::object::builder::{BodyDesc, ColliderDesc};
+use super::object::{
+    Body, BodyHandle, BodySet, Collider, ColliderHandle, ColliderSet, ColliderState,
+};
+use super::world::{PhysicsWorld, TeleportContacts};
+use crate::{PhysicsError, Scalar, Vec2, FP};
+
+/// Owns the `PhysicsWorld` together with its bodies and colliders.
+/// The fields stay public for everything the split interface offers beyond these shortcuts.
+pub struct World<T, N = FP> {
+    pub physics: PhysicsWorld<T, N>,
+    pub bodies: BodySet<N>,
+    pub colliders: ColliderSet<T, N>,
+}
+
+impl<T: Copy, N: Scalar> Default for World<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy, N: Scalar> World<T, N> {
+    pub fn new() -> Self {
+        Self {
+            physics: PhysicsWorld::new(),
+            bodies: BodySet::new(),
+            colliders: ColliderSet::new(),
+        }
+    }
+    pub fn add_body(&mut self, desc: BodyDesc<N>) -> BodyHandle {
+        self.bodies.insert(desc.build())
+    }
+    /// Attaches a collider to an existing body.
+    pub fn add_collider(
+        &mut self,
+        desc: ColliderDesc<T, N>,
+        owner: BodyHandle,
+    ) -> Result<ColliderHandle, PhysicsError> {
+        self.colliders
+            .try_insert(desc.build(owner), &mut self.bodies, &mut self.physics)
+    }
+    /// Removes the body together with all of its colliders.
+    pub fn remove_body(&mut self, handle: BodyHandle) -> Result<(), PhysicsError> {
+        self.physics
+            .try_remove_body(handle, &mut self.bodies, &mut self.colliders)
+    }
+    pub fn remove_collider(&mut self, handle: ColliderHandle) -> Result<(), PhysicsError> {
+        self.physics
+            .try_remove_collider(handle, &mut self.bodies, &mut self.colliders)
+    }
+    pub fn set_collider_owner(
+        &mut self,
+        handle: ColliderHandle,
+        owner: BodyHandle,
+    ) -> Result<(), PhysicsError> {
+        self.physics
+            .set_collider_owner(handle, owner, &mut self.bodies, &mut self.colliders)
+    }
+    pub fn set_collider_shape(
+        &mut self,
+        handle: ColliderHandle,
+        shape: impl Into<Shape<N>>,
+    ) -> Result<(), PhysicsError> {
+        self.physics
+            .set_collider_shape(handle, shape, &mut self.bodies, &mut self.colliders)
+    }
+    pub fn set_collider_offset(
+        &mut self,
+        handle: ColliderHandle,
+        offset: Vec2<N>,
+    ) -> Result<(), PhysicsError> {
+        self.physics
+            .set_collider_offset(handle, offset, &mut self.bodies, &mut self.colliders)
+    }
+    pub fn set_collider_state(
+        &mut self,
+        handle: ColliderHandle,
+        state: ColliderState,
+    ) -> Result<(), PhysicsError> {
+        self.physics
+            .set_collider_state(handle, state, &mut self.bodies, &mut self.colliders)
+    }
+    /// See `PhysicsWorld::set_body_enabled`.
+    pub fn set_body_enabled(
+        &mut self,
+        handle: BodyHandle,
+        enabled: bool,
+    ) -> Result<(), PhysicsError> {
+        self.physics
+            .set_body_enabled(handle, enabled, &mut self.bodies, &self.colliders)
+    }
+    pub fn set_collider_enabled(
+        &mut self,
+        handle: ColliderHandle,
+        enabled: bool,
+    ) -> Result<(), PhysicsError> {
+        self.physics
+            .set_collider_enabled(handle, enabled, &mut self.bodies, &mut self.colliders)
+    }
+    /// See `PhysicsWorld::teleport`.
+    pub fn teleport(
+        &mut self,
+        handle: BodyHandle,
+        position: Vec2<N>,
+        contacts: TeleportContacts,
+    ) -> Result<(), PhysicsError> {
+        self.physics.teleport(
+            handle,
+            position,
+            contacts,
+            &mut self.bodies,
+            &self.colliders,
+        )
+    }
+    pub fn body(&self, handle: BodyHandle) -> Option<&Body<N>> {
+        self.bodies.get(handle)
+    }
+    pub fn body_mut(&mut self, handle: BodyHandle) -> Option<&mut Body<N>> {
+        self.bodies.get_mut(handle)
+    }
+    pub fn collider(&self, handle: ColliderHandle) -> Option<&Collider<T, N>> {
+        self.colliders.get(handle)
+    }
+    pub fn collider_mut(&mut self, handle: ColliderHandle) -> Option<&mut Collider<T, N>> {
+        self.colliders.get_mut(handle)
+    }
+    pub fn step(&mut self, dt: N) {
+        self.physics.step(dt, &mut self.bodies, &mut self.colliders);
+    }
+    /// Events generated by the last `step`.
+    pub fn events(&self) -> &[ContactEvent<T>] {
+        self.physics.events()
+    }
+    pub fn steps(&self) -> u64 {
+        self.physics.steps()
+    }
+    /// Ongoing interactions with their duration in steps, see `PhysicsWorld::active_interactions`.
+    pub fn active_interactions(
+        &self,
+    ) -> impl Iterator<Item = (ColliderHandle, ColliderHandle, &Interaction<N>, u64)> {
+        self.physics.active_interactions()
+    }
+    /// Body-level collision events generated by the last `step`.
+    pub fn body_events(&self) -> &[BodyContactEvent<N>] {
+        self.physics.body_events()
+    }
+    /// Body-level sensor events generated by the last `step`.
+    pub fn sensor_events(&self) -> &[SensorEvent<T>] {
+        self.physics.sensor_events()
+    }
+    /// Bodies inside the sensor, see `PhysicsWorld::occupants_of`.
+    pub fn occupants_of(
+        &self,
+        handle: ColliderHandle,
+    ) -> Result<impl Iterator<Item = BodyHandle>, PhysicsError> {
+        self.physics.occupants_of(handle, &self.colliders)
+    }
+    pub fn interactions_of(
+        &self,
+        handle: ColliderHandle,
+    ) -> Result<impl Iterator<Item = (ColliderHandle, &Interaction<N>)>, PhysicsError> {
+        self.physics.try_interactions_of(handle)
+    }
+    /// Colliders overlapping the `AABB` centered at `position`.
+    pub fn overlap_test(
+        &self,
+        position: Vec2<N>,
+        half_exts: Vec2<N>,
+        collision_mask: u32,
+    ) -> impl Iterator<Item = ColliderHandle> + '_ {
+        self.physics.overlap_test(
+            position,
+            half_exts,
+            collision_mask,
+            &self.bodies,
+            &self.colliders,
+        )
+    }
+    pub fn project_ray<'a>(
+        &'a self,
+        ray: &'a Ray<N>,
+        collision_mask: u32,
+    ) -> impl Iterator<Item = (ColliderHandle, Raycast<N>)> + 'a {
+        self.physics
+            .project_ray(ray, collision_mask, &self.bodies, &self.colliders)
+    }
+    /// `toi` of the `Raycast` is the fraction of `motion` travelled before touching.
+    pub fn cast_shape<'a>(
+        &'a self,
+        shape: &'a Shape<N>,
+        position: Vec2<N>,
+        motion: Vec2<N>,
+        collision_mask: u32,
+    ) -> impl Iterator<Item = (ColliderHandle, Raycast<N>)> + 'a {
+        self.physics.cast_shape(
+            shape,
+            position,
+            motion,
+            collision_mask,
+            &self.bodies,
+            &self.colliders,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{add_static, add_with_collider, dt, floor, square};
+    use crate::to_fp;
+
+    #[test]
+    fn owns_bodies_and_colliders() {
+        let mut world = World::<&str>::new();
+        add_static(&mut world, Vec2::zero(), floor("floor"));
+        let (player, player_collider) = add_with_collider(
+            &mut world,
+            BodyDesc::new()
+                .with_position(Vec2::from(0, -40))
+                .with_velocity(Vec2::from(0, 600)),
+            ColliderDesc::new(square(), "player"),
+        );
+
+        for _ in 0..4 {
+            world.step(dt());
+        }
+        assert!(matches!(
+            world.events(),
+            [ContactEvent::CollisionStarted(..)]
+        ));
+        assert_eq!(world.body(player).unwrap().position.y(), to_fp(-16));
+        assert_eq!(world.interactions_of(player_collider).unwrap().count(), 1);
+
+        let ray = Ray {
+            origin: Vec2::from(0, -100),
+            dir: Vec2::from(0, 200),
+            toi: to_fp(1),
+        };
+        assert_eq!(world.project_ray(&ray, u32::MAX).count(), 2);
+        assert_eq!(
+            world
+                .overlap_test(Vec2::from(0, -16), Vec2::from(4, 4), u32::MAX)
+                .collect::<Vec<_>>(),
+            vec![player_collider]
+        );
+
+        world.remove_body(player).unwrap();
+        assert_eq!(
+            world.remove_collider(player_collider),
+            Err(PhysicsError::ColliderNotFound(player_collider))
+        );
+        world.step(dt());
+        assert!(matches!(world.events(), [ContactEvent::CollisionEnded(..)]));
+    }
+}
@@ -1,127 +1,196 @@
-use fixed::traits::ToFixed;
-use fixed_sqrt::FixedSqrt;
-use num_traits::{abs, clamp_max, clamp_min, Float, PrimInt};
+use az::Cast;
+use num_traits::{Float, PrimInt};
 use std::fmt;
 
-use std::ops::{Add, Div, Mul, Sub};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
-use crate::{to_fp, FP};
+use crate::{Scalar, FP};
 
-#[derive(Default, Copy, Clone, Debug)]
-pub struct Vec2 {
-    pub x: FP,
-    pub y: FP,
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub struct Vec2<N = FP> {
+    pub x: N,
+    pub y: N,
 }
 
-impl Vec2 {
-    pub fn new(x: FP, y: FP) -> Self {
-        Self { x: x, y: y }
+impl<N: Scalar> Vec2<N> {
+    pub fn new(x: N, y: N) -> Self {
+        Self { x, y }
     }
 
-    pub fn x(&self) -> FP {
+    pub fn x(&self) -> N {
         self.x
     }
 
-    pub fn y(&self) -> FP {
+    pub fn y(&self) -> N {
         self.y
     }
 
-    pub fn x_mut(&mut self) -> &mut FP {
+    pub fn x_mut(&mut self) -> &mut N {
         &mut self.x
     }
 
-    pub fn y_mut(&mut self) -> &mut FP {
+    pub fn y_mut(&mut self) -> &mut N {
         &mut self.y
     }
 
-    pub fn set_x(&mut self, value: impl fixed::traits::ToFixed) {
-        self.x = to_fp(value);
+    pub fn set_x(&mut self, value: impl Cast<N>) {
+        self.x = value.cast();
     }
 
-    pub fn set_y(&mut self, value: impl fixed::traits::ToFixed) {
-        self.y = to_fp(value);
+    pub fn set_y(&mut self, value: impl Cast<N>) {
+        self.y = value.cast();
     }
 
     pub fn zero() -> Self {
-        Self::new(FP::from_num(0), FP::from_num(0))
+        Self::new(N::zero(), N::zero())
     }
 
     pub fn one() -> Self {
-        Self::new(FP::from_num(1), FP::from_num(1))
+        Self::new(N::one(), N::one())
     }
 
     pub fn unit_x() -> Self {
-        Self::new(FP::from_num(1.0), FP::from_num(0))
+        Self::new(N::one(), N::zero())
     }
 
     pub fn unit_y() -> Self {
-        Self::new(FP::from_num(0), FP::from_num(1.0))
+        Self::new(N::zero(), N::one())
     }
 
-    pub fn from<T: ToFixed>(x: T, y: T) -> Self {
-        Self::new(FP::from_num(x), FP::from_num(y))
+    pub fn from<T: Cast<N>>(x: T, y: T) -> Self {
+        Self::new(x.cast(), y.cast())
     }
 
-    pub fn from_float<T: Float + ToFixed>(x: T, y: T) -> Self {
-        Self::new(FP::from_num(x), FP::from_num(y))
+    pub fn from_float<T: Float + Cast<N>>(x: T, y: T) -> Self {
+        Self::new(x.cast(), y.cast())
     }
 
-    pub fn from_int<T: PrimInt + ToFixed>(x: T, y: T) -> Self {
-        Self::new(FP::from_num(x), FP::from_num(y))
+    pub fn from_int<T: PrimInt + Cast<N>>(x: T, y: T) -> Self {
+        Self::new(x.cast(), y.cast())
     }
 
     // Creates a vector with all elements set to value
-    pub fn splat(value: FP) -> Self {
+    pub fn splat(value: N) -> Self {
         Self::new(value, value)
     }
 
     pub fn min(self, other: Self) -> Self {
-        Self::new(clamp_min(self.x, other.x), clamp_min(self.y, other.y))
+        Self::new(self.x.partial_min(other.x), self.y.partial_min(other.y))
     }
 
     pub fn max(self, other: Self) -> Self {
-        Self::new(clamp_max(self.x, other.x), clamp_max(self.y, other.y))
+        Self::new(self.x.partial_max(other.x), self.y.partial_max(other.y))
     }
 
-    pub fn add_scalar(self, value: impl fixed::traits::ToFixed) -> Self {
-        let fixed_value = to_fp(value);
-        Self::new(self.x + fixed_value, self.y + fixed_value)
+    pub fn add_scalar(self, value: impl Cast<N>) -> Self {
+        let value = value.cast();
+        Self::new(self.x + value, self.y + value)
     }
 
-    pub fn sub_scalar(self, value: impl fixed::traits::ToFixed) -> Self {
-        let fixed_value = to_fp(value);
-        Self::new(self.x - fixed_value, self.y - fixed_value)
+    pub fn sub_scalar(self, value: impl Cast<N>) -> Self {
+        let value = value.cast();
+        Self::new(self.x - value, self.y - value)
     }
 
-    pub fn mul_scalar(self, value: impl fixed::traits::ToFixed) -> Self {
-        let fixed_value = to_fp(value);
-        Self::new(self.x * fixed_value, self.y * fixed_value)
+    pub fn mul_scalar(self, value: impl Cast<N>) -> Self {
+        let value = value.cast();
+        Self::new(self.x * value, self.y * value)
     }
 
-    pub fn div_scalar(self, value: impl fixed::traits::ToFixed) -> Self {
-        let fixed_value = to_fp(value);
-        Self::new(self.x / fixed_value, self.y / fixed_value)
+    pub fn div_scalar(self, value: impl Cast<N>) -> Self {
+        let value = value.cast();
+        Self::new(self.x / value, self.y / value)
     }
 
     pub fn abs(&self) -> Self {
-        Self::new(abs(self.x), abs(self.y))
+        Self::new(self.x.abs(), self.y.abs())
     }
 
     // x^2 + y^2 = length^2
-    pub fn length(&self) -> FP {
+    pub fn length(&self) -> N {
         ((self.x * self.x) + (self.y * self.y)).sqrt()
     }
 
-    pub fn dot(self, other: Self) -> FP {
+    pub fn dot(self, other: Self) -> N {
         (self.x * other.x) + (self.y * other.y)
     }
 
     pub fn normalized(&self) -> Self {
         Self::new(self.x / self.length(), self.y / self.length())
     }
+
+    pub fn length_squared(&self) -> N {
+        self.dot(*self)
+    }
+
+    pub fn distance(self, other: Self) -> N {
+        (other - self).length()
+    }
+
+    pub fn distance_squared(self, other: Self) -> N {
+        (other - self).length_squared()
+    }
+
+    /// z component of the 3D cross product, positive if `other` is clockwise from `self` (y axis points down)
+    pub fn cross(self, other: Self) -> N {
+        (self.x * other.y) - (self.y * other.x)
+    }
+
+    /// Rotated by 90 degrees, clockwise on screen (y axis points down)
+    pub fn perp(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// `t` of 0 returns `self`, 1 returns `other`, values outside of the range extrapolate
+    pub fn lerp(self, other: Self, t: N) -> Self {
+        self + (other - self) * t
+    }
+
+    /// Clamps every component separately
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        self.max(min).min(max)
+    }
+
+    /// Part of the vector parallel to `other`, zero if `other` is zero
+    pub fn project(self, other: Self) -> Self {
+        let length_squared = other.length_squared();
+        if length_squared == N::zero() {
+            return Self::zero();
+        }
+        other * (self.dot(other) / length_squared)
+    }
+
+    /// Part of the vector perpendicular to `other`, `self` if `other` is zero
+    pub fn reject(self, other: Self) -> Self {
+        self - self.project(other)
+    }
+
+    /// Unit vector pointing at `angle` radians from the x axis
+    pub fn from_angle(angle: N) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(cos, sin)
+    }
+
+    /// Angle from the x axis in the `-PI..=PI` range
+    pub fn angle(&self) -> N {
+        self.y.atan2(self.x)
+    }
+
+    /// Signed angle needed to rotate `self` onto `other`
+    pub fn angle_to(self, other: Self) -> N {
+        self.cross(other).atan2(self.dot(other))
+    }
+
+    pub fn rotated(self, angle: N) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(
+            (self.x * cos) - (self.y * sin),
+            (self.x * sin) + (self.y * cos),
+        )
+    }
 }
 
-impl Add for Vec2 {
+impl<N: Scalar> Add for Vec2<N> {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
@@ -129,7 +198,7 @@ impl Add for Vec2 {
     }
 }
 
-impl Sub for Vec2 {
+impl<N: Scalar> Sub for Vec2<N> {
     type Output = Self;
 
     fn sub(self, other: Self) -> Self {
@@ -137,7 +206,7 @@ impl Sub for Vec2 {
     }
 }
 
-impl Mul for Vec2 {
+impl<N: Scalar> Mul for Vec2<N> {
     type Output = Self;
 
     fn mul(self, other: Self) -> Self {
@@ -145,7 +214,7 @@ impl Mul for Vec2 {
     }
 }
 
-impl Div for Vec2 {
+impl<N: Scalar> Div for Vec2<N> {
     type Output = Self;
 
     fn div(self, other: Self) -> Self {
@@ -153,8 +222,146 @@ impl Div for Vec2 {
     }
 }
 
-impl fmt::Display for Vec2 {
+impl<N: Scalar> Mul<N> for Vec2<N> {
+    type Output = Self;
+
+    fn mul(self, value: N) -> Self {
+        Self::new(self.x * value, self.y * value)
+    }
+}
+
+impl<N: Scalar> Div<N> for Vec2<N> {
+    type Output = Self;
+
+    fn div(self, value: N) -> Self {
+        Self::new(self.x / value, self.y / value)
+    }
+}
+
+impl<N: Scalar> Neg for Vec2<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<N: Scalar> AddAssign for Vec2<N> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<N: Scalar> SubAssign for Vec2<N> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<N: Scalar> MulAssign for Vec2<N> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<N: Scalar> MulAssign<N> for Vec2<N> {
+    fn mul_assign(&mut self, value: N) {
+        *self = *self * value;
+    }
+}
+
+impl<N: Scalar> DivAssign for Vec2<N> {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+impl<N: Scalar> DivAssign<N> for Vec2<N> {
+    fn div_assign(&mut self, value: N) {
+        *self = *self / value;
+    }
+}
+
+impl<N: Scalar> fmt::Display for Vec2<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "({0}, {1})", self.x, self.y)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::to_fp;
+
+    fn close(a: FP, b: f64) -> bool {
+        (a.to_num::<f64>() - b).abs() < 0.001
+    }
+
+    #[test]
+    fn operators() {
+        let mut v = Vec2::<FP>::from(3, -4);
+        assert_eq!(-v, Vec2::from(-3, 4));
+        assert_eq!(v * to_fp(2), Vec2::from(6, -8));
+        assert_eq!(v / to_fp(2), Vec2::from_float(1.5, -2.));
+        v += Vec2::one();
+        assert_eq!(v, Vec2::from(4, -3));
+        v -= Vec2::unit_x();
+        v *= to_fp(2);
+        assert_eq!(v, Vec2::from(6, -6));
+        v *= Vec2::from(1, -1);
+        v /= to_fp(3);
+        assert_eq!(v, Vec2::splat(to_fp(2)));
+        assert_eq!(Vec2::<FP>::default(), Vec2::zero());
+    }
+
+    #[test]
+    fn products_and_distances() {
+        let a = Vec2::<FP>::from(3, 4);
+        let b = Vec2::<FP>::from(-1, 2);
+        assert_eq!(a.length_squared(), to_fp(25));
+        assert_eq!(a.length(), to_fp(5));
+        assert_eq!(a.distance_squared(b), to_fp(20));
+        assert_eq!(a.cross(b), to_fp(10));
+        assert_eq!(b.cross(a), to_fp(-10));
+        assert_eq!(a.perp(), Vec2::from(-4, 3));
+        assert_eq!(a.perp().dot(a), to_fp(0));
+        assert_eq!(a.min(b), Vec2::from(-1, 2));
+        assert_eq!(a.max(b), Vec2::from(3, 4));
+        assert_eq!(a.lerp(b, to_fp(0.5)), Vec2::from(1, 3));
+        assert_eq!(
+            Vec2::<FP>::from(-5, 5).clamp(Vec2::zero(), Vec2::splat(to_fp(2))),
+            Vec2::from(0, 2)
+        );
+        assert_eq!(a.project(Vec2::unit_x()), Vec2::from(3, 0));
+        assert_eq!(a.reject(Vec2::unit_x()), Vec2::from(0, 4));
+        assert_eq!(a.project(Vec2::zero()), Vec2::zero());
+    }
+
+    #[test]
+    fn angles_and_rotation() {
+        use std::f64::consts::{FRAC_PI_2, PI};
+
+        assert_eq!(Vec2::<FP>::unit_x().angle(), to_fp(0));
+        assert!(close(Vec2::<FP>::unit_y().angle(), FRAC_PI_2));
+        assert!(close(Vec2::<FP>::from(-1, 0).angle(), PI));
+        assert!(close(Vec2::<FP>::from(-3, -3).angle(), -3. * PI / 4.));
+        assert!(close(Vec2::<FP>::from(1, -2).angle(), (-2f64).atan2(1.)));
+
+        let direction = Vec2::<FP>::from_angle(to_fp(PI / 3.));
+        assert!(close(direction.x(), 0.5));
+        assert!(close(direction.y(), (PI / 3.).sin()));
+
+        let rotated = Vec2::<FP>::from(2, 0).rotated(to_fp(-FRAC_PI_2));
+        assert!(close(rotated.x(), 0.) && close(rotated.y(), -2.));
+        // angles outside of `-PI..PI` wrap around
+        let rotated = Vec2::<FP>::from(2, 0).rotated(to_fp(5. * PI));
+        assert!(close(rotated.x(), -2.) && close(rotated.y(), 0.));
+
+        let a = Vec2::<FP>::from(1, 1);
+        assert!(close(a.angle_to(a.perp()), FRAC_PI_2));
+        assert!(close(a.perp().angle_to(a), -FRAC_PI_2));
+
+        let float = Vec2::<f32>::from_angle(1.).rotated(0.5);
+        assert!((float.angle() - 1.5).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,256 @@
+use super::debug_draw::{debug_draw, DebugColor, DebugDraw, DebugDrawStyle};
+use super::object::{BodySet, ColliderSet};
+use super::world::PhysicsWorld;
+use crate::{Scalar, Vec2, FP};
+use std::fmt::Write;
+
+/// Part of the world exported by `export_svg` and the size of the resulting image in pixels.
+#[derive(Copy, Clone, Debug)]
+pub struct SvgViewport<N = FP> {
+    /// Top left corner of the exported area
+    pub min: Vec2<N>,
+    /// Bottom right corner of the exported area
+    pub max: Vec2<N>,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl<N: Scalar> SvgViewport<N> {
+    /// Image size matches the size of the area.
+    pub fn new(min: Vec2<N>, max: Vec2<N>) -> Self {
+        let size = max - min;
+        Self {
+            min,
+            max,
+            width: size.x().to_f64().ceil().max(1.) as u32,
+            height: size.y().to_f64().ceil().max(1.) as u32,
+        }
+    }
+    /// Smallest area containing every collider, extended by `margin` on every side.
+    pub fn fit<T>(bodies: &BodySet<N>, colliders: &ColliderSet<T, N>, margin: Vec2<N>) -> Self {
+        let mut bounds: Option<(Vec2<N>, Vec2<N>)> = None;
+        for (_, collider) in colliders.iter() {
+            let center = bodies[collider.owner].position + collider.offset;
+            let min = center - collider.shape.half_exts();
+            let max = center + collider.shape.half_exts();
+            bounds = Some(match bounds {
+                Some((bounds_min, bounds_max)) => (bounds_min.min(min), bounds_max.max(max)),
+                None => (min, max),
+            });
+        }
+        let (min, max) = bounds.unwrap_or((Vec2::zero(), Vec2::zero()));
+        Self::new(min - margin, max + margin)
+    }
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+}
+
+/// `DebugDraw` backend accumulating SVG elements. Finish with `into_svg`.
+pub struct SvgDraw<N = FP> {
+    viewport: SvgViewport<N>,
+    scale: (f64, f64),
+    elements: String,
+}
+
+impl<N: Scalar> SvgDraw<N> {
+    pub fn new(viewport: SvgViewport<N>) -> Self {
+        let size = viewport.max - viewport.min;
+        let scale = |pixels: u32, world: f64| {
+            if world > 0. {
+                f64::from(pixels) / world
+            } else {
+                1.
+            }
+        };
+        Self {
+            viewport,
+            scale: (
+                scale(viewport.width, size.x().to_f64()),
+                scale(viewport.height, size.y().to_f64()),
+            ),
+            elements: String::new(),
+        }
+    }
+    /// Wraps the drawn elements in a complete SVG document.
+    pub fn into_svg(self) -> String {
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n{2}</svg>\n",
+            self.viewport.width, self.viewport.height, self.elements
+        )
+    }
+    fn point(&self, position: Vec2<N>) -> (f64, f64) {
+        let local = position - self.viewport.min;
+        (
+            local.x().to_f64() * self.scale.0,
+            local.y().to_f64() * self.scale.1,
+        )
+    }
+}
+
+impl<N: Scalar> DebugDraw<N> for SvgDraw<N> {
+    fn draw_rect(&mut self, position: Vec2<N>, half_exts: Vec2<N>, color: DebugColor) {
+        let (x, y) = self.point(position - half_exts);
+        let (x2, y2) = self.point(position + half_exts);
+        // writing into a String can't fail
+        let _ = writeln!(
+            self.elements,
+            "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"{}\" fill-opacity=\"0.3\" stroke=\"{}\" stroke-width=\"2\"/>",
+            x,
+            y,
+            x2 - x,
+            y2 - y,
+            rgb(color),
+            rgb(color)
+        );
+    }
+    fn draw_line(&mut self, from: Vec2<N>, to: Vec2<N>, color: DebugColor) {
+        let (x1, y1) = self.point(from);
+        let (x2, y2) = self.point(to);
+        let _ = writeln!(
+            self.elements,
+            "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"{}\" stroke-opacity=\"{}\" stroke-width=\"2\"/>",
+            x1, y1, x2, y2, rgb(color), color.a
+        );
+    }
+    fn draw_point(&mut self, position: Vec2<N>, color: DebugColor) {
+        let (x, y) = self.point(position);
+        let _ = writeln!(
+            self.elements,
+            "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"3\" fill=\"{}\" fill-opacity=\"{}\"/>",
+            x,
+            y,
+            rgb(color),
+            color.a
+        );
+    }
+    fn draw_polygon(&mut self, vertices: &[Vec2<N>], color: DebugColor) {
+        let points: Vec<String> = vertices
+            .iter()
+            .map(|vertex| {
+                let (x, y) = self.point(*vertex);
+                format!("{:.2},{:.2}", x, y)
+            })
+            .collect();
+        let _ = writeln!(
+            self.elements,
+            "<polygon points=\"{}\" fill=\"{}\" fill-opacity=\"0.3\" stroke=\"{}\" stroke-width=\"2\"/>",
+            points.join(" "),
+            rgb(color),
+            rgb(color)
+        );
+    }
+    fn draw_circle(&mut self, center: Vec2<N>, radius: N, color: DebugColor) {
+        let (x, y) = self.point(center);
+        let radius = radius.to_f64();
+        let _ = writeln!(
+            self.elements,
+            "<ellipse cx=\"{:.2}\" cy=\"{:.2}\" rx=\"{:.2}\" ry=\"{:.2}\" fill=\"{}\" fill-opacity=\"0.3\" stroke=\"{}\" stroke-width=\"2\"/>",
+            x,
+            y,
+            radius * self.scale.0,
+            radius * self.scale.1,
+            rgb(color),
+            rgb(color)
+        );
+    }
+    fn draw_text(&mut self, position: Vec2<N>, text: &str, color: DebugColor) {
+        let (x, y) = self.point(position);
+        let _ = writeln!(
+            self.elements,
+            "<text x=\"{:.2}\" y=\"{:.2}\" font-family=\"monospace\" font-size=\"12\" fill=\"{}\">{}</text>",
+            x,
+            y,
+            rgb(color),
+            escape(text)
+        );
+    }
+}
+
+fn rgb(color: DebugColor) -> String {
+    let channel = |value: f32| (value.clamp(0., 1.) * 255.).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(color.r),
+        channel(color.g),
+        channel(color.b)
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Renders the world into an SVG document, doesn't need anything besides the standard library
+/// so it can be attached to failing tests or bug reports, e.g. with `std::fs::write`.
+///
+/// Colliders are colored by state and status, bodies are marked by their origin with velocity arrows
+/// and active contacts from the `CollisionGraph` are drawn the same way as `debug_draw` does.
+pub fn export_svg<T: Copy, N: Scalar>(
+    viewport: SvgViewport<N>,
+    world: &PhysicsWorld<T, N>,
+    bodies: &BodySet<N>,
+    colliders: &ColliderSet<T, N>,
+) -> String {
+    let style = DebugDrawStyle {
+        body_origins: true,
+        velocity_arrows: true,
+        ..DebugDrawStyle::default()
+    };
+    let mut draw = SvgDraw::new(viewport);
+    debug_draw(&mut draw, &style, world, bodies, colliders, &[]);
+    draw.into_svg()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{BodyDesc, ColliderDesc};
+    use crate::AABB;
+
+    #[test]
+    fn maps_the_viewport_onto_the_image() {
+        let mut world = PhysicsWorld::<()>::new();
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+        let coin = bodies.insert(
+            BodyDesc::new()
+                .with_position(Vec2::from(8, 8))
+                .make_static()
+                .build(),
+        );
+        colliders.insert(
+            ColliderDesc::new(
+                AABB {
+                    half_exts: Vec2::from(8, 8),
+                },
+                (),
+            )
+            .sensor()
+            .build(coin),
+            &mut bodies,
+            &mut world,
+        );
+
+        let viewport = SvgViewport::fit(&bodies, &colliders, Vec2::from(4, 4));
+        assert_eq!((viewport.width, viewport.height), (24, 24));
+        let svg = export_svg(viewport.with_size(48, 48), &world, &bodies, &colliders);
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("width=\"48\" height=\"48\""));
+        // the margin of 4 units is 8 pixels wide at twice the scale
+        assert!(svg.contains("<rect x=\"8.00\" y=\"8.00\" width=\"32.00\" height=\"32.00\""));
+        assert!(svg.contains("<circle cx=\"24.00\" cy=\"24.00\""));
+        assert!(svg.contains(&rgb(DebugDrawStyle::<FP>::default().sensor)));
+    }
+
+    #[test]
+    fn escapes_text() {
+        assert_eq!(escape("<a & b>"), "&lt;a &amp; b&gt;");
+    }
+}
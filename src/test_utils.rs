@@ -0,0 +1,47 @@
+//! Fixtures shared by the tests of several modules.
+use crate::builder::{BodyDesc, ColliderDesc};
+use crate::{to_fp, BodyHandle, ColliderHandle, Vec2, World, AABB, FP};
+
+pub(crate) fn dt() -> FP {
+    to_fp(1. / 60.)
+}
+
+/// 16x16 box, the usual size of a character or crate.
+pub(crate) fn square() -> AABB {
+    AABB {
+        half_exts: Vec2::from(8, 8),
+    }
+}
+
+/// 200x16 floor, boxes rest on it at y = -16 when it's centered at the origin.
+pub(crate) fn floor<T: Copy>(user_tag: T) -> ColliderDesc<T> {
+    ColliderDesc::new(
+        AABB {
+            half_exts: Vec2::from(100, 8),
+        },
+        user_tag,
+    )
+}
+
+/// Static body at `position` with a single collider.
+pub(crate) fn add_static<T: Copy>(
+    world: &mut World<T>,
+    position: Vec2,
+    collider: ColliderDesc<T>,
+) -> (BodyHandle, ColliderHandle) {
+    add_with_collider(
+        world,
+        BodyDesc::new().with_position(position).make_static(),
+        collider,
+    )
+}
+
+pub(crate) fn add_with_collider<T: Copy>(
+    world: &mut World<T>,
+    body: BodyDesc,
+    collider: ColliderDesc<T>,
+) -> (BodyHandle, ColliderHandle) {
+    let body = world.add_body(body);
+    let collider = world.add_collider(collider, body).unwrap();
+    (body, collider)
+}
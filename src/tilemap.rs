@@ -0,0 +1,355 @@
+use super::collision::AABB;
+use super::object::builder::{BodyDesc, ColliderDesc};
+use super::object::{BodyHandle, BodySet, ColliderHandle, ColliderSet};
+use super::world::PhysicsWorld;
+use crate::{Scalar, Vec2, FP};
+use fxhash::FxHashMap;
+
+/// Rectangle of cells in grid coordinates, `x` and `y` point at its top left cell.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct TileRect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Greedily merges solid cells into rectangles.
+/// `cells` are stored row by row, `width` cells per row.
+/// Every rectangle is first extended as far as possible to the right and only then downwards,
+/// so the same grid always produces the same rectangles in the same order.
+pub fn merge_tiles(cells: &[bool], width: usize, height: usize) -> Vec<TileRect> {
+    assert_eq!(
+        cells.len(),
+        width * height,
+        "Tilemap cells don't match its dimensions"
+    );
+    let mut visited = vec![false; cells.len()];
+    let mut rects = Vec::new();
+    let free = |visited: &[bool], x: usize, y: usize| {
+        let index = y * width + x;
+        cells[index] && !visited[index]
+    };
+
+    for y in 0..height {
+        for x in 0..width {
+            if !free(&visited, x, y) {
+                continue;
+            }
+
+            let mut rect_width = 1;
+            while x + rect_width < width && free(&visited, x + rect_width, y) {
+                rect_width += 1;
+            }
+
+            let mut rect_height = 1;
+            while y + rect_height < height
+                && (x..x + rect_width).all(|column| free(&visited, column, y + rect_height))
+            {
+                rect_height += 1;
+            }
+
+            for row in y..y + rect_height {
+                for column in x..x + rect_width {
+                    visited[row * width + column] = true;
+                }
+            }
+            rects.push(TileRect {
+                x,
+                y,
+                width: rect_width,
+                height: rect_height,
+            });
+        }
+    }
+    rects
+}
+
+/// Builder for the `Tilemap`. Start with `new`, finish with `build`.
+#[derive(Debug, Clone)]
+pub struct TilemapDesc<T, N = FP> {
+    /// Position of the top left corner of the grid
+    pub position: Vec2<N>,
+    pub width: usize,
+    pub height: usize,
+    /// Full width and height of a single cell
+    pub tile_size: Vec2<N>,
+    /// Solidity of the cells, stored row by row
+    pub cells: Vec<bool>,
+    /// Template for every merged collider, its shape and offset are overwritten
+    pub collider: ColliderDesc<T, N>,
+}
+
+impl<T: Copy, N: Scalar> TilemapDesc<T, N> {
+    pub fn new(width: usize, height: usize, tile_size: Vec2<N>, user_tag: T) -> Self {
+        Self {
+            position: Vec2::zero(),
+            width,
+            height,
+            tile_size,
+            cells: vec![false; width * height],
+            collider: ColliderDesc::new(AABB::default(), user_tag),
+        }
+    }
+    pub fn with_position(mut self, position: Vec2<N>) -> Self {
+        self.position = position;
+        self
+    }
+    /// Panics if the amount of cells doesn't match the dimensions.
+    pub fn with_cells(mut self, cells: Vec<bool>) -> Self {
+        assert_eq!(
+            cells.len(),
+            self.width * self.height,
+            "Tilemap cells don't match its dimensions"
+        );
+        self.cells = cells;
+        self
+    }
+    /// Panics if the cell is outside of the grid.
+    pub fn with_solid(mut self, x: usize, y: usize, solid: bool) -> Self {
+        assert!(
+            x < self.width && y < self.height,
+            "Cell ({}, {}) is outside of the tilemap",
+            x,
+            y
+        );
+        self.cells[y * self.width + x] = solid;
+        self
+    }
+    pub fn with_collider(mut self, collider: ColliderDesc<T, N>) -> Self {
+        self.collider = collider;
+        self
+    }
+    /// Inserts a static body with the merged colliders.
+    pub fn build(
+        self,
+        world: &mut PhysicsWorld<T, N>,
+        bodies: &mut BodySet<N>,
+        colliders: &mut ColliderSet<T, N>,
+    ) -> Tilemap<T, N> {
+        let body = bodies.insert(
+            BodyDesc::new()
+                .with_position(self.position)
+                .make_static()
+                .build(),
+        );
+        let mut tilemap = Tilemap {
+            width: self.width,
+            height: self.height,
+            tile_size: self.tile_size,
+            cells: self.cells,
+            collider: self.collider,
+            body,
+            rects: Vec::new(),
+        };
+        tilemap.rebuild(world, bodies, colliders);
+        tilemap
+    }
+}
+
+/// Grid of cells represented by as few `AABB` colliders as the greedy merge allows.
+/// All colliders belong to a single static body placed at the top left corner of the grid.
+#[derive(Debug, Clone)]
+pub struct Tilemap<T, N = FP> {
+    width: usize,
+    height: usize,
+    tile_size: Vec2<N>,
+    cells: Vec<bool>,
+    collider: ColliderDesc<T, N>,
+    body: BodyHandle,
+    rects: Vec<(TileRect, ColliderHandle)>,
+}
+
+impl<T: Copy, N: Scalar> Tilemap<T, N> {
+    pub fn body(&self) -> BodyHandle {
+        self.body
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn tile_size(&self) -> Vec2<N> {
+        self.tile_size
+    }
+    /// Cells outside of the grid are treated as empty.
+    pub fn is_solid(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.cells[y * self.width + x]
+    }
+    /// Merged rectangles with the colliders representing them.
+    pub fn rects(&self) -> impl Iterator<Item = (TileRect, ColliderHandle)> + '_ {
+        self.rects.iter().copied()
+    }
+    /// Panics if the cell is outside of the grid.
+    /// Only the colliders whose rectangles changed are replaced, removed ones send their Ended events next frame.
+    pub fn set_solid(
+        &mut self,
+        x: usize,
+        y: usize,
+        solid: bool,
+        world: &mut PhysicsWorld<T, N>,
+        bodies: &mut BodySet<N>,
+        colliders: &mut ColliderSet<T, N>,
+    ) {
+        assert!(
+            x < self.width && y < self.height,
+            "Cell ({}, {}) is outside of the tilemap",
+            x,
+            y
+        );
+        let index = y * self.width + x;
+        if self.cells[index] != solid {
+            self.cells[index] = solid;
+            self.rebuild(world, bodies, colliders);
+        }
+    }
+    /// Removes the tilemap's body together with all of its colliders.
+    pub fn remove(
+        self,
+        world: &mut PhysicsWorld<T, N>,
+        bodies: &mut BodySet<N>,
+        colliders: &mut ColliderSet<T, N>,
+    ) {
+        world.remove_body(self.body, bodies, colliders);
+    }
+
+    fn rebuild(
+        &mut self,
+        world: &mut PhysicsWorld<T, N>,
+        bodies: &mut BodySet<N>,
+        colliders: &mut ColliderSet<T, N>,
+    ) {
+        let mut existing: FxHashMap<TileRect, ColliderHandle> =
+            self.rects.iter().copied().collect();
+        let merged = merge_tiles(&self.cells, self.width, self.height);
+
+        let mut rects = Vec::with_capacity(merged.len());
+        for rect in merged {
+            let handle = match existing.remove(&rect) {
+                Some(handle) => handle,
+                None => {
+                    let collider = self
+                        .collider
+                        .clone()
+                        .with_shape(AABB {
+                            half_exts: self.rect_size(rect) * N::from_f64(0.5),
+                        })
+                        .with_offset(self.rect_center(rect))
+                        .build(self.body);
+                    colliders
+                        .insert(collider, bodies, world)
+                        .expect("Tilemap body is missing")
+                }
+            };
+            rects.push((rect, handle));
+        }
+
+        // remove in the previous order to keep the Ended events deterministic
+        for (rect, handle) in self.rects.iter() {
+            if existing.contains_key(rect) {
+                world.remove_collider(*handle, bodies, colliders);
+            }
+        }
+        self.rects = rects;
+    }
+
+    fn rect_size(&self, rect: TileRect) -> Vec2<N> {
+        self.tile_size * grid_vec(rect.width, rect.height)
+    }
+
+    fn rect_center(&self, rect: TileRect) -> Vec2<N> {
+        let corner = self.tile_size * grid_vec(rect.x, rect.y);
+        corner + self.rect_size(rect) * N::from_f64(0.5)
+    }
+}
+
+fn grid_vec<N: Scalar>(x: usize, y: usize) -> Vec2<N> {
+    Vec2::new(N::from_f64(x as f64), N::from_f64(y as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::to_fp;
+
+    #[test]
+    #[should_panic(expected = "outside of the tilemap")]
+    fn rejects_cells_outside_of_the_grid() {
+        // would wrap around into the next row without the check
+        TilemapDesc::<()>::new(3, 2, Vec2::from(16, 16), ()).with_solid(3, 0, true);
+    }
+
+    #[test]
+    fn merges_into_rectangles() {
+        #[rustfmt::skip]
+        let cells = [
+            true, true, true,
+            true, true, false,
+            false, false, true,
+        ];
+        let rects = merge_tiles(&cells, 3, 3);
+        assert_eq!(
+            rects,
+            vec![
+                TileRect {
+                    x: 0,
+                    y: 0,
+                    width: 3,
+                    height: 1
+                },
+                TileRect {
+                    x: 0,
+                    y: 1,
+                    width: 2,
+                    height: 1
+                },
+                TileRect {
+                    x: 2,
+                    y: 2,
+                    width: 1,
+                    height: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn updates_only_changed_colliders() {
+        let mut world = PhysicsWorld::<()>::new();
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        let mut tilemap = TilemapDesc::new(4, 2, Vec2::from(16, 16), ())
+            .with_cells(vec![true; 8])
+            .build(&mut world, &mut bodies, &mut colliders);
+        assert_eq!(tilemap.rects().count(), 1);
+        let (_, handle) = tilemap.rects().next().unwrap();
+        let half_exts = colliders[handle].shape.half_exts();
+        assert_eq!((half_exts.x(), half_exts.y()), (to_fp(32), to_fp(16)));
+        let offset = colliders[handle].offset;
+        assert_eq!((offset.x(), offset.y()), (to_fp(32), to_fp(16)));
+
+        tilemap.set_solid(3, 1, false, &mut world, &mut bodies, &mut colliders);
+        let rects: Vec<_> = tilemap.rects().map(|(rect, _)| rect).collect();
+        assert_eq!(
+            rects,
+            vec![
+                TileRect {
+                    x: 0,
+                    y: 0,
+                    width: 4,
+                    height: 1
+                },
+                TileRect {
+                    x: 0,
+                    y: 1,
+                    width: 3,
+                    height: 1
+                },
+            ]
+        );
+        assert_eq!(colliders.iter().count(), 2);
+        assert_eq!(bodies[tilemap.body()].colliders.len(), 2);
+    }
+}
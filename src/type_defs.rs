@@ -1,11 +1,20 @@
 //use fixed::prelude::*;
-use fixed::types::I48F16;
+use fixed::consts::{FRAC_PI_2, PI, TAU};
+use fixed::traits::{Fixed, FixedSigned};
+use fixed::types::extra::{
+    IsLessOrEqual, LeEqU128, LeEqU16, LeEqU32, LeEqU64, True, U12, U124, U28, U60,
+};
+use fixed::types::{I48F16, I4F28};
+use fixed::{FixedI128, FixedI16, FixedI32, FixedI64};
+use std::fmt::{Debug, Display};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 //use fixed_macro::fixed;
 
 // define fixed point used
 pub type FP = I48F16;
 
 // EPSILON is equal to the "error" in precision, or the "step" between numbers in FP
+#[allow(non_snake_case)]
 pub fn EPSILON() -> FP {
     FP::from_num(1 / (2 ^ 16))
 }
@@ -13,3 +22,178 @@ pub fn EPSILON() -> FP {
 pub fn to_fp(num: impl fixed::traits::ToFixed) -> FP {
     FP::from_num(num)
 }
+
+/// Number type the engine computes with, `FP` is used unless specified otherwise.
+///
+/// Implemented for signed fixed point numbers with at least 4 integer bits including the sign, which are needed
+/// to represent `TAU` (e.g. `I32F32` for more fractional precision, but not `I4F28`), and for `f32`/`f64`,
+/// which are faster but give up determinism across platforms.
+pub trait Scalar:
+    Copy
+    + Default
+    + Debug
+    + Display
+    + PartialEq
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+    + 'static
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    /// Smallest representable step for fixed point numbers, machine epsilon for floats
+    fn epsilon() -> Self;
+    fn from_i32(value: i32) -> Self;
+    fn from_f64(value: f64) -> Self;
+    fn to_f64(self) -> f64;
+    fn abs(self) -> Self;
+    /// `-1`, `0` or `1`
+    fn signum(self) -> Self;
+    fn sqrt(self) -> Self;
+    /// Sine and cosine of an angle in radians
+    fn sin_cos(self) -> (Self, Self);
+    /// Four quadrant arctangent of `self / x` in the `-PI..=PI` range, `0` if both are `0`
+    fn atan2(self, x: Self) -> Self;
+    // named differently than `Ord::min` and `Ord::max` to avoid ambiguity for fixed point types
+    fn partial_min(self, other: Self) -> Self {
+        if other < self {
+            other
+        } else {
+            self
+        }
+    }
+    fn partial_max(self, other: Self) -> Self {
+        if other > self {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+macro_rules! impl_scalar_fixed {
+    ($($Fixed:ident, $LeEqU:ident, $MaxFrac:ident;)*) => {$(
+        impl<Frac: $LeEqU + IsLessOrEqual<$MaxFrac, Output = True>> Scalar for $Fixed<Frac>
+        where
+            $Fixed<Frac>: FixedSigned,
+        {
+            fn zero() -> Self {
+                Self::ZERO
+            }
+            fn one() -> Self {
+                Self::from_num(1)
+            }
+            fn epsilon() -> Self {
+                Self::DELTA
+            }
+            fn from_i32(value: i32) -> Self {
+                Self::from_num(value)
+            }
+            fn from_f64(value: f64) -> Self {
+                Self::from_num(value)
+            }
+            fn to_f64(self) -> f64 {
+                self.to_num()
+            }
+            fn abs(self) -> Self {
+                FixedSigned::abs(self)
+            }
+            fn signum(self) -> Self {
+                FixedSigned::signum(self)
+            }
+            fn sqrt(self) -> Self {
+                Fixed::sqrt(self)
+            }
+            fn sin_cos(self) -> (Self, Self) {
+                // reduced to `-PI..PI` in the original type so the angle fits into `I4F28`
+                let pi = Self::from_num(PI);
+                let angle = (self + pi).rem_euclid(Self::from_num(TAU)) - pi;
+                let (sin, cos) = cordic::sin_cos(I4F28::from_num(angle));
+                (Self::from_num(sin), Self::from_num(cos))
+            }
+            fn atan2(self, x: Self) -> Self {
+                let (y_abs, x_abs) = (FixedSigned::abs(self), FixedSigned::abs(x));
+                if y_abs == Self::ZERO && x_abs == Self::ZERO {
+                    return Self::ZERO;
+                }
+                // the ratio never exceeds 1, folding the remaining octants by symmetry
+                let octant = if y_abs <= x_abs {
+                    Self::from_num(cordic::atan(I4F28::from_num(y_abs / x_abs)))
+                } else {
+                    Self::from_num(FRAC_PI_2)
+                        - Self::from_num(cordic::atan(I4F28::from_num(x_abs / y_abs)))
+                };
+                let angle = if x < Self::ZERO {
+                    Self::from_num(PI) - octant
+                } else {
+                    octant
+                };
+                if self < Self::ZERO {
+                    -angle
+                } else {
+                    angle
+                }
+            }
+        }
+    )*};
+}
+
+impl_scalar_fixed! {
+    FixedI16, LeEqU16, U12;
+    FixedI32, LeEqU32, U28;
+    FixedI64, LeEqU64, U60;
+    FixedI128, LeEqU128, U124;
+}
+
+macro_rules! impl_scalar_float {
+    ($($Float:ident),*) => {$(
+        impl Scalar for $Float {
+            fn zero() -> Self {
+                0.
+            }
+            fn one() -> Self {
+                1.
+            }
+            fn epsilon() -> Self {
+                $Float::EPSILON
+            }
+            fn from_i32(value: i32) -> Self {
+                value as $Float
+            }
+            fn from_f64(value: f64) -> Self {
+                value as $Float
+            }
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+            fn abs(self) -> Self {
+                $Float::abs(self)
+            }
+            fn signum(self) -> Self {
+                if self == 0. {
+                    0.
+                } else {
+                    $Float::signum(self)
+                }
+            }
+            fn sqrt(self) -> Self {
+                $Float::sqrt(self)
+            }
+            fn sin_cos(self) -> (Self, Self) {
+                $Float::sin_cos(self)
+            }
+            fn atan2(self, x: Self) -> Self {
+                $Float::atan2(self, x)
+            }
+        }
+    )*};
+}
+
+impl_scalar_float!(f32, f64);
@@ -1,63 +1,114 @@
-use super::collision::{CollisionGraph, CollisionInfo, Interaction, Ray, Raycast};
-use super::event::ContactEvent;
+use super::collision::{
+    cast_shape, CollisionGraph, CollisionInfo, Interaction, InteractionEdge, Ray, Raycast, Shape,
+};
+use super::event::{BodyContactEvent, ContactEvent, SensorEvent};
 use super::object::{
-    collision_manifold, is_colliding, is_penetrating, Body, BodyHandle, BodySet, BodyStatus,
+    collision_contact, is_colliding, is_penetrating, Body, BodyHandle, BodySet, BodyStatus,
     Collider, ColliderHandle, ColliderSet, ColliderState,
 };
-use crate::{to_fp, Vec2, FP};
+use super::object::{solver_reach, solver_stop};
+use crate::{LayerInteraction, LayerMatrix, PhysicsError, Scalar, Vec2, FP};
+use fxhash::{FxHashMap, FxHashSet};
+use petgraph::visit::EdgeRef;
+use std::cmp::Ordering;
+
+/// What `PhysicsWorld::teleport` does with the interactions of the body.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TeleportContacts {
+    /// Left for the next step, which ends the ones that no longer hold
+    Keep,
+    /// Ended right away, the ones still holding at the new position start again during the next step
+    Clear,
+    /// Updated for the new position right away, including the ones that only start there
+    Reevaluate,
+}
 
 /// T - User supplied type used as a tag, present in all events
-pub struct PhysicsWorld<T> {
-    pub collision_graph: CollisionGraph,
+/// N - Number type used for all computations, see `Scalar`
+pub struct PhysicsWorld<T, N = FP> {
+    pub collision_graph: CollisionGraph<N>,
+    /// Named layers of colliders created with `ColliderDesc::with_layer`
+    pub layers: LayerMatrix,
+    /// Kinematic bodies without velocity fall asleep after this many steps, 0 (the default) disables sleeping
+    pub sleep_steps: u32,
     pub(crate) events: Vec<ContactEvent<T>>,
-    removal_events: Vec<ContactEvent<T>>,
+    // completed steps
+    steps: u64,
+    // events of removals and teleports, sent with the ones of the next step
+    pending_events: Vec<ContactEvent<T>>,
     body_handles: Vec<BodyHandle>,
+    pub(crate) sensor_events: Vec<SensorEvent<T>>,
+    // bodies inside every sensor at the end of the last step
+    occupants: Vec<(ColliderHandle, T, Vec<BodyHandle>)>,
+    pub(crate) body_events: Vec<BodyContactEvent<N>>,
+    // colliding pairs of bodies at the end of the last step
+    body_contacts: Vec<(BodyHandle, BodyHandle, Vec2<N>)>,
 }
 
-impl<T: Copy> Default for PhysicsWorld<T> {
+impl<T: Copy, N: Scalar> Default for PhysicsWorld<T, N> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<T: Copy> PhysicsWorld<T> {
+impl<T: Copy, N: Scalar> PhysicsWorld<T, N> {
     pub fn new() -> Self {
         Self {
             collision_graph: CollisionGraph::with_capacity(128, 16),
+            layers: LayerMatrix::new(),
+            sleep_steps: 0,
             events: Vec::with_capacity(16),
-            removal_events: Vec::with_capacity(8),
+            steps: 0,
+            pending_events: Vec::with_capacity(8),
             body_handles: Vec::with_capacity(16),
+            sensor_events: Vec::new(),
+            occupants: Vec::new(),
+            body_events: Vec::new(),
+            body_contacts: Vec::new(),
         }
     }
-    /// Panics if there's no collider associated with the handle.  
+    /// Panics if there's no collider associated with the handle, see `try_remove_collider`.  
     /// When collider has active collisions/overlaps the Ended event is scheduled to be sent next frame.
     pub fn remove_collider(
         &mut self,
         handle: ColliderHandle,
-        bodies: &mut BodySet,
-        colliders: &mut ColliderSet<T>,
+        bodies: &mut BodySet<N>,
+        colliders: &mut ColliderSet<T, N>,
     ) {
-        #[cfg(debug_assertions)]
-        if colliders.get(handle).is_none() {
-            panic!("Trying to delete nonexistent collider {:?}", handle)
-        }
+        self.try_remove_collider(handle, bodies, colliders)
+            .unwrap_or_else(|error| panic!("Trying to delete collider: {}", error))
+    }
+    /// Same as `remove_collider`, nothing is removed when it returns an error.
+    pub fn try_remove_collider(
+        &mut self,
+        handle: ColliderHandle,
+        bodies: &mut BodySet<N>,
+        colliders: &mut ColliderSet<T, N>,
+    ) -> Result<(), PhysicsError> {
+        colliders.try_get(handle)?;
+        self.collision_graph.try_get_node_index(handle)?;
         let collider = colliders.internal_remove(handle);
         let collision_graph = &mut self.collision_graph;
-        let removal_events = &mut self.removal_events;
+        let pending_events = &mut self.pending_events;
 
         // schedule collision/overlap ended events
-        let node_index = collision_graph.get_node_index(handle);
-        for node_index_other in collision_graph.src.neighbors(node_index) {
-            let handle_other = *collision_graph
-                .src
-                .node_weight(node_index_other)
-                .expect("remove_collider: other node missing");
+        for (handle_other, interaction) in collision_graph.edges(handle) {
             let collider_other = &colliders[handle_other];
-            let event =
-                ContactEvent::new(handle, &collider, handle_other, collider_other).into_finished();
-            removal_events.push(event);
+            // whatever rested on the collider has to react to it missing
+            if let Some(body) = bodies.get_mut(collider_other.owner) {
+                body.wake_up();
+            }
+            let event = ContactEvent::with_interaction(
+                handle,
+                &collider,
+                handle_other,
+                collider_other,
+                interaction,
+            )
+            .into_finished();
+            push_event(pending_events, &collider, collider_other, event);
         }
-        collision_graph.remove_node(handle);
+        collision_graph.try_remove_node(handle)?;
 
         // if owner doesn't exist it's assumed both collider and body are getting removed
         if let Some(body) = bodies.get_mut(collider.owner) {
@@ -75,23 +126,287 @@ impl<T: Copy> PhysicsWorld<T> {
                 )
             }
         }
+        Ok(())
     }
-    /// Panics if there's no body associated with the handle.  
+    /// Panics if there's no body associated with the handle, see `try_remove_body`.  
     /// All associated colliders are also removed.
     /// When any collider has active collisions/overlaps the Ended event is scheduled to be sent next frame.
     pub fn remove_body(
         &mut self,
         handle: BodyHandle,
-        bodies: &mut BodySet,
-        colliders: &mut ColliderSet<T>,
+        bodies: &mut BodySet<N>,
+        colliders: &mut ColliderSet<T, N>,
     ) {
-        #[cfg(debug_assertions)]
-        if bodies.get(handle).is_none() {
-            panic!("Trying to delete nonexistent body {:?}", handle)
+        self.try_remove_body(handle, bodies, colliders)
+            .unwrap_or_else(|error| panic!("Trying to delete body: {}", error))
+    }
+    /// Same as `remove_body`, nothing is removed when it returns an error.
+    pub fn try_remove_body(
+        &mut self,
+        handle: BodyHandle,
+        bodies: &mut BodySet<N>,
+        colliders: &mut ColliderSet<T, N>,
+    ) -> Result<(), PhysicsError> {
+        for collider_handle in bodies.try_get(handle)?.colliders.iter() {
+            colliders.try_get(*collider_handle)?;
+            self.collision_graph.try_get_node_index(*collider_handle)?;
         }
         let body = bodies.internal_remove(handle);
         for collider_handle in body.colliders.into_iter() {
-            self.remove_collider(collider_handle, bodies, colliders);
+            self.try_remove_collider(collider_handle, bodies, colliders)?;
+        }
+        Ok(())
+    }
+
+    /// Moves the body without sweeping it through everything in between, unlike a step.  
+    /// Events of the interactions cleared or reevaluated are sent with the ones of the next step.
+    pub fn teleport(
+        &mut self,
+        handle: BodyHandle,
+        position: Vec2<N>,
+        contacts: TeleportContacts,
+        bodies: &mut BodySet<N>,
+        colliders: &ColliderSet<T, N>,
+    ) -> Result<(), PhysicsError> {
+        let body = bodies.try_get_mut(handle)?;
+        body.position = position;
+        body.rest_position = position;
+        body.wake_up();
+        wake_touching(handle, bodies, colliders, &self.collision_graph);
+
+        let graph = &mut self.collision_graph;
+        for coll_handle in bodies[handle].colliders.iter() {
+            let node = graph.try_get_node_index(*coll_handle)?;
+            let others: Vec<ColliderHandle> = match contacts {
+                TeleportContacts::Keep => continue,
+                TeleportContacts::Clear => {
+                    graph.src.neighbors(node).map(|n| graph.src[n]).collect()
+                }
+                TeleportContacts::Reevaluate => colliders
+                    .iter()
+                    .map(|(other, _)| other)
+                    .filter(|other| other != coll_handle)
+                    .collect(),
+            };
+            for other in others {
+                let other_node = graph.try_get_node_index(other)?;
+                let edge = graph.src.find_edge(node, other_node);
+                // events keep the order of the pair stored in the graph
+                let (handle1, handle2) = match edge.and_then(|edge| graph.src.edge_endpoints(edge))
+                {
+                    Some((node1, node2)) => (graph.src[node1], graph.src[node2]),
+                    None => (*coll_handle, other),
+                };
+                let (collider1, collider2) = (&colliders[handle1], &colliders[handle2]);
+                let previous = edge.and_then(|edge| graph.src[edge].interaction.clone());
+                let current = match contacts {
+                    TeleportContacts::Reevaluate => current_interaction(
+                        &self.layers,
+                        &bodies[collider1.owner],
+                        collider1,
+                        &bodies[collider2.owner],
+                        collider2,
+                    ),
+                    _ => None,
+                };
+                let started = push_interaction_events(
+                    &mut self.pending_events,
+                    (handle1, collider1),
+                    (handle2, collider2),
+                    previous.as_ref(),
+                    current.as_ref(),
+                );
+                // the started events are sent with the next step
+                let started = if started {
+                    self.steps + 1
+                } else {
+                    edge.map_or(0, |edge| graph.src[edge].started)
+                };
+                match (edge, current) {
+                    (Some(edge), None) => {
+                        graph.src.remove_edge(edge);
+                    }
+                    (Some(edge), interaction) => {
+                        graph.src[edge] = InteractionEdge {
+                            interaction,
+                            started,
+                        }
+                    }
+                    (None, Some(current)) => {
+                        let edge = InteractionEdge {
+                            interaction: Some(current),
+                            started,
+                        };
+                        graph.src.add_edge(node, other_node, edge);
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Moves the collider to another body, the bodies' collider lists are kept in sync.  
+    /// Interactions affected by the change send their events during the next step.
+    pub fn set_collider_owner(
+        &mut self,
+        handle: ColliderHandle,
+        owner: BodyHandle,
+        bodies: &mut BodySet<N>,
+        colliders: &mut ColliderSet<T, N>,
+    ) -> Result<(), PhysicsError> {
+        let collider = colliders.try_get_mut(handle)?;
+        let new_body = bodies.try_get_mut(owner)?;
+        if let (Shape::Chain(_), BodyStatus::Kinematic) = (&collider.shape, new_body.status) {
+            return Err(PhysicsError::ChainOnKinematicBody(owner));
+        }
+        let previous_owner = std::mem::replace(&mut collider.owner, owner);
+        if previous_owner == owner {
+            return Ok(());
+        }
+        new_body.colliders.push(handle);
+        if let Some(previous_body) = bodies.get_mut(previous_owner) {
+            previous_body.colliders.retain(|owned| *owned != handle);
+            previous_body.wake_up();
+        }
+        self.collider_changed(handle, bodies, colliders);
+        Ok(())
+    }
+    /// Resizes or reshapes the collider, a `SegmentChain` only fits a static body.
+    pub fn set_collider_shape(
+        &mut self,
+        handle: ColliderHandle,
+        shape: impl Into<Shape<N>>,
+        bodies: &mut BodySet<N>,
+        colliders: &mut ColliderSet<T, N>,
+    ) -> Result<(), PhysicsError> {
+        let shape = shape.into();
+        let collider = colliders.try_get_mut(handle)?;
+        let body = bodies.try_get(collider.owner)?;
+        if let (Shape::Chain(_), BodyStatus::Kinematic) = (&shape, body.status) {
+            return Err(PhysicsError::ChainOnKinematicBody(collider.owner));
+        }
+        collider.shape = shape;
+        self.collider_changed(handle, bodies, colliders);
+        Ok(())
+    }
+    pub fn set_collider_offset(
+        &mut self,
+        handle: ColliderHandle,
+        offset: Vec2<N>,
+        bodies: &mut BodySet<N>,
+        colliders: &mut ColliderSet<T, N>,
+    ) -> Result<(), PhysicsError> {
+        colliders.try_get_mut(handle)?.offset = offset;
+        self.collider_changed(handle, bodies, colliders);
+        Ok(())
+    }
+    /// Switches between solid and sensor, active interactions end and start again with the new kind.
+    pub fn set_collider_state(
+        &mut self,
+        handle: ColliderHandle,
+        state: ColliderState,
+        bodies: &mut BodySet<N>,
+        colliders: &mut ColliderSet<T, N>,
+    ) -> Result<(), PhysicsError> {
+        colliders.try_get_mut(handle)?.state = state;
+        self.collider_changed(handle, bodies, colliders);
+        Ok(())
+    }
+    /// A disabled body keeps its handle and colliders, but is skipped by stepping, queries and events.  
+    /// Disabling ends its interactions during the next step, enabling lets the next step detect them again.
+    pub fn set_body_enabled(
+        &mut self,
+        handle: BodyHandle,
+        enabled: bool,
+        bodies: &mut BodySet<N>,
+        colliders: &ColliderSet<T, N>,
+    ) -> Result<(), PhysicsError> {
+        let body = bodies.try_get_mut(handle)?;
+        if body.enabled == enabled {
+            return Ok(());
+        }
+        body.enabled = enabled;
+        body.wake_up();
+        if enabled {
+            wake_touching(handle, bodies, colliders, &self.collision_graph);
+        } else {
+            for coll_handle in bodies[handle].colliders.clone() {
+                self.end_interactions(coll_handle, bodies, colliders);
+            }
+        }
+        Ok(())
+    }
+    /// Same as `set_body_enabled` for a single collider, which stays inactive while its body is disabled.
+    pub fn set_collider_enabled(
+        &mut self,
+        handle: ColliderHandle,
+        enabled: bool,
+        bodies: &mut BodySet<N>,
+        colliders: &mut ColliderSet<T, N>,
+    ) -> Result<(), PhysicsError> {
+        let collider = colliders.try_get_mut(handle)?;
+        if collider.enabled == enabled {
+            return Ok(());
+        }
+        collider.enabled = enabled;
+        if !enabled {
+            self.end_interactions(handle, bodies, colliders);
+        }
+        self.collider_changed(handle, bodies, colliders);
+        Ok(())
+    }
+    // schedules the ended events of the collider's interactions and forgets them
+    fn end_interactions(
+        &mut self,
+        handle: ColliderHandle,
+        bodies: &mut BodySet<N>,
+        colliders: &ColliderSet<T, N>,
+    ) {
+        let graph = &mut self.collision_graph;
+        let Ok(node) = graph.try_get_node_index(handle) else {
+            return;
+        };
+        let others: Vec<_> = graph.src.neighbors(node).collect();
+        for other_node in others {
+            let Some(edge) = graph.src.find_edge(node, other_node) else {
+                continue;
+            };
+            let (node1, node2) = graph.src.edge_endpoints(edge).unwrap();
+            let (handle1, handle2) = (graph.src[node1], graph.src[node2]);
+            if let Some(interaction) = graph
+                .src
+                .remove_edge(edge)
+                .and_then(|edge| edge.interaction)
+            {
+                let (collider1, collider2) = (&colliders[handle1], &colliders[handle2]);
+                let event = ContactEvent::with_interaction(
+                    handle1,
+                    collider1,
+                    handle2,
+                    collider2,
+                    &interaction,
+                )
+                .into_finished();
+                push_event(&mut self.pending_events, collider1, collider2, event);
+            }
+            // whatever rested on the collider has to react to it missing
+            let other = graph.src[other_node];
+            if let Some(body) = bodies.get_mut(colliders[other].owner) {
+                body.wake_up();
+            }
+        }
+    }
+    // wakes everything the change could affect, so the next step updates the interactions
+    fn collider_changed(
+        &self,
+        handle: ColliderHandle,
+        bodies: &mut BodySet<N>,
+        colliders: &ColliderSet<T, N>,
+    ) {
+        let owner = colliders[handle].owner;
+        if let Some(body) = bodies.get_mut(owner) {
+            body.wake_up();
+            wake_touching(owner, bodies, colliders, &self.collision_graph);
         }
     }
 
@@ -100,43 +415,81 @@ impl<T: Copy> PhysicsWorld<T> {
     pub fn interactions_of(
         &self,
         handle: ColliderHandle,
-    ) -> impl Iterator<Item = (crate::ColliderHandle, &Interaction)> {
+    ) -> impl Iterator<Item = (crate::ColliderHandle, &Interaction<N>)> {
         self.collision_graph.edges(handle)
     }
+    /// Same as `interactions_of`, but returns an error for colliders that aren't in this world.
+    pub fn try_interactions_of(
+        &self,
+        handle: ColliderHandle,
+    ) -> Result<impl Iterator<Item = (crate::ColliderHandle, &Interaction<N>)>, PhysicsError> {
+        self.collision_graph.try_edges(handle)
+    }
     /// Interactions are defined per collider.  
     /// To get only collisions or overlaps use `collisions_of` or `overlaps_of` respectively.  
+    /// Panics for colliders that aren't in this world, see `try_collisions_of`.
     pub fn collisions_of(
         &self,
         handle: ColliderHandle,
-    ) -> impl Iterator<Item = (crate::ColliderHandle, &CollisionInfo)> {
-        self.collision_graph
-            .edges(handle)
-            .filter_map(|(h, interaction)| Some((h, interaction.collision()?)))
+    ) -> impl Iterator<Item = (crate::ColliderHandle, &CollisionInfo<N>)> {
+        self.try_collisions_of(handle)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+    /// Same as `collisions_of`, but returns an error for colliders that aren't in this world.
+    pub fn try_collisions_of(
+        &self,
+        handle: ColliderHandle,
+    ) -> Result<impl Iterator<Item = (crate::ColliderHandle, &CollisionInfo<N>)>, PhysicsError>
+    {
+        Ok(self
+            .collision_graph
+            .try_edges(handle)?
+            .filter_map(|(h, interaction)| Some((h, interaction.collision()?))))
     }
     /// Interactions are defined per collider.  
     /// To get only collisions or overlaps use `collisions_of` or `overlaps_of` respectively.  
+    /// Panics for colliders that aren't in this world, see `try_overlaps_of`.
     pub fn overlaps_of(
         &self,
         handle: ColliderHandle,
-    ) -> impl Iterator<Item = (crate::ColliderHandle, &Interaction)> {
-        self.collision_graph
-            .edges(handle)
-            .filter(|(_h, interaction)| interaction.is_overlap())
+    ) -> impl Iterator<Item = (crate::ColliderHandle, &Interaction<N>)> {
+        self.try_overlaps_of(handle)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+    /// Same as `overlaps_of`, but returns an error for colliders that aren't in this world.
+    pub fn try_overlaps_of(
+        &self,
+        handle: ColliderHandle,
+    ) -> Result<impl Iterator<Item = (crate::ColliderHandle, &Interaction<N>)>, PhysicsError> {
+        Ok(self
+            .collision_graph
+            .try_edges(handle)?
+            .filter(|(_h, interaction)| interaction.is_overlap()))
+    }
+    /// Bodies overlapping the collider, usually a sensor, each listed once however many of their colliders overlap it.
+    pub fn occupants_of(
+        &self,
+        handle: ColliderHandle,
+        colliders: &ColliderSet<T, N>,
+    ) -> Result<impl Iterator<Item = BodyHandle>, PhysicsError> {
+        Ok(overlapping_bodies(handle, colliders, &self.collision_graph)?.into_iter())
     }
     /// Returns an iterator to `ColliderHandle`'s of colliders overlapping with given AABB.  
     ///  `position` is the center of the AABB
     pub fn overlap_test<'a>(
         &self,
-        position: Vec2,
-        half_exts: Vec2,
+        position: Vec2<N>,
+        half_exts: Vec2<N>,
         collision_mask: u32,
-        bodies: &'a BodySet,
-        colliders: &'a ColliderSet<T>,
+        bodies: &'a BodySet<N>,
+        colliders: &'a ColliderSet<T, N>,
     ) -> impl Iterator<Item = ColliderHandle> + 'a {
         // TODO: Use broadphase
         bodies
             .iter()
+            .filter(|(_, body)| body.enabled)
             .flat_map(|(_, body)| body.colliders.iter().map(move |h| (*h, body.position)))
+            .filter(move |(h, _)| colliders[*h].enabled)
             .filter(move |(h, _)| (colliders[*h].category_bits & collision_mask) != 0)
             .filter_map(move |(h, body_pos)| {
                 if colliders[h].overlaps_aabb(body_pos, position, half_exts) {
@@ -149,15 +502,17 @@ impl<T: Copy> PhysicsWorld<T> {
     /// Returns an iterator to `ColliderHandle`'s of colliders overlapping with given ray.  
     pub fn project_ray<'a>(
         &self,
-        ray: &'a Ray,
+        ray: &'a Ray<N>,
         collision_mask: u32,
-        bodies: &'a BodySet,
-        colliders: &'a ColliderSet<T>,
-    ) -> impl Iterator<Item = (ColliderHandle, Raycast)> + 'a {
+        bodies: &'a BodySet<N>,
+        colliders: &'a ColliderSet<T, N>,
+    ) -> impl Iterator<Item = (ColliderHandle, Raycast<N>)> + 'a {
         // TODO: Use broadphase
         bodies
             .iter()
+            .filter(|(_, body)| body.enabled)
             .flat_map(|(_, body)| body.colliders.iter().map(move |h| (*h, body.position)))
+            .filter(move |(h, _)| colliders[*h].enabled)
             .filter(move |(h, _)| (colliders[*h].category_bits & collision_mask) != 0)
             .filter_map(move |(h, pos)| {
                 colliders[h]
@@ -165,32 +520,143 @@ impl<T: Copy> PhysicsWorld<T> {
                     .map(|raycast| (h, raycast))
             })
     }
+    /// Returns an iterator to `ColliderHandle`'s of colliders hit by `shape` moving from `position` by `motion`.  
+    /// `toi` of the `Raycast` is the fraction of `motion` travelled before touching.
+    pub fn cast_shape<'a>(
+        &self,
+        shape: &'a Shape<N>,
+        position: Vec2<N>,
+        motion: Vec2<N>,
+        collision_mask: u32,
+        bodies: &'a BodySet<N>,
+        colliders: &'a ColliderSet<T, N>,
+    ) -> impl Iterator<Item = (ColliderHandle, Raycast<N>)> + 'a {
+        // TODO: Use broadphase
+        bodies
+            .iter()
+            .filter(|(_, body)| body.enabled)
+            .flat_map(|(_, body)| body.colliders.iter().map(move |h| (*h, body.position)))
+            .filter(move |(h, _)| colliders[*h].enabled)
+            .filter(move |(h, _)| (colliders[*h].category_bits & collision_mask) != 0)
+            .filter_map(move |(h, pos)| {
+                let collider = &colliders[h];
+                cast_shape(
+                    shape,
+                    position,
+                    motion,
+                    &collider.shape,
+                    pos + collider.offset,
+                )
+                .map(|raycast| (h, raycast))
+            })
+    }
+    /// Events of the last step together with the ones of removals and teleports before it.  
+    /// Sorted by `ContactEvent::pair`, the events of a single pair keep the order they happened in,
+    /// so the order only depends on the handles and not on the history of insertions and removals.
     pub fn events(&self) -> &Vec<ContactEvent<T>> {
         &self.events
     }
+    /// Number of steps taken so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+    /// Every ongoing interaction with the number of steps it lasted since the one it started during,
+    /// 0 on the step it started.
+    pub fn active_interactions(
+        &self,
+    ) -> impl Iterator<Item = (ColliderHandle, ColliderHandle, &Interaction<N>, u64)> {
+        let graph = &self.collision_graph.src;
+        graph.edge_references().filter_map(move |edge| {
+            let weight = edge.weight();
+            Some((
+                graph[edge.source()],
+                graph[edge.target()],
+                weight.interaction.as_ref()?,
+                self.steps.saturating_sub(weight.started),
+            ))
+        })
+    }
+    /// Pairs of bodies which started or stopped colliding during the last step, sorted by their handles.
+    pub fn body_events(&self) -> &Vec<BodyContactEvent<N>> {
+        &self.body_events
+    }
+    /// Bodies which entered or exited sensors during the last step, sorted by the handles of the sensor and body.
+    pub fn sensor_events(&self) -> &Vec<SensorEvent<T>> {
+        &self.sensor_events
+    }
 
-    pub fn step(&mut self, dt: FP, bodies: &mut BodySet, colliders: &mut ColliderSet<T>) {
+    pub fn step(&mut self, dt: N, bodies: &mut BodySet<N>, colliders: &mut ColliderSet<T, N>) {
         self.events.clear();
-        self.events.append(&mut self.removal_events);
+        self.events.append(&mut self.pending_events);
+        self.steps += 1;
         self.body_handles.clear();
 
         let collision_graph = &mut self.collision_graph;
+        let layers = &self.layers;
         let events = &mut self.events;
         let body_handles = &mut self.body_handles;
 
         body_handles.extend(bodies.iter().map(|(h, _)| h));
 
-        // compute the new maximum movement for every body
-        for (_, body) in bodies.iter_mut() {
-            if let BodyStatus::Kinematic = body.status {
-                body.movement = body.velocity.mul_scalar(dt);
+        // compute the new maximum movement for every body, the ones standing still fall asleep
+        for handle in body_handles.iter() {
+            let body = &mut bodies[*handle];
+            if !body.enabled {
+                continue;
+            }
+            match body.status {
+                BodyStatus::Kinematic => {
+                    body.update_sleep(self.sleep_steps);
+                    body.movement = body.velocity * dt;
+                }
+                BodyStatus::Static => {
+                    if body.was_moved() {
+                        wake_touching(*handle, bodies, colliders, collision_graph);
+                    }
+                }
             }
         }
 
-        step_x(bodies, colliders, body_handles);
-        step_y(bodies, colliders, collision_graph, body_handles);
+        step_x(bodies, colliders, layers, body_handles);
+        step_y(bodies, colliders, layers, collision_graph, body_handles);
+
+        describe_collisions(
+            bodies,
+            colliders,
+            layers,
+            collision_graph,
+            events,
+            self.steps,
+        );
+        // stable, so the events of a pair stay in order
+        events.sort_by_key(ContactEvent::pair);
+        self.sensor_events.clear();
+        update_occupants(
+            colliders,
+            collision_graph,
+            &mut self.occupants,
+            &mut self.sensor_events,
+        );
+        self.sensor_events.sort_by_key(|event| match event {
+            SensorEvent::Entered(sensor, body, _) | SensorEvent::Exited(sensor, body, _) => {
+                (*sensor, *body)
+            }
+        });
+        self.body_events.clear();
+        update_body_contacts(
+            colliders,
+            collision_graph,
+            &mut self.body_contacts,
+            &mut self.body_events,
+        );
+        self.body_events.sort_by_key(|event| match event {
+            BodyContactEvent::Started(body1, body2, _)
+            | BodyContactEvent::Ended(body1, body2, _) => (*body1, *body2),
+        });
 
-        describe_collisions(bodies, colliders, collision_graph, events);
+        for (_, body) in bodies.iter_mut() {
+            body.rest_position = body.position;
+        }
 
         // for (h1, _h2, manifold) in manifolds.iter() {
         //     let body = bodies.get_mut(*h1).expect("Body missing post collision");
@@ -203,12 +669,17 @@ impl<T: Copy> PhysicsWorld<T> {
     }
 }
 
-fn step_x<T>(bodies: &mut BodySet, colliders: &mut ColliderSet<T>, body_handles: &[BodyHandle]) {
+fn step_x<T, N: Scalar>(
+    bodies: &mut BodySet<N>,
+    colliders: &mut ColliderSet<T, N>,
+    layers: &LayerMatrix,
+    body_handles: &[BodyHandle],
+) {
     for body1_handle in body_handles {
         let body1 = bodies.get(*body1_handle).expect("Collider without a body");
         let mut move_x = body1.movement.x();
 
-        if let BodyStatus::Static = body1.status {
+        if !body1.is_awake() {
             continue;
         }
 
@@ -229,39 +700,55 @@ fn step_x<T>(bodies: &mut BodySet, colliders: &mut ColliderSet<T>, body_handles:
                     continue;
                 }
 
-                // for x step we skip sensors completely
-                if let ColliderState::Sensor = collider2.state {
-                    continue;
-                }
-
-                if !can_collide(body1, collider1, collider2) {
+                // for x step we skip sensors and overlapping pairs completely
+                if !matches!(
+                    pair_state(layers, body1, collider1, collider2),
+                    Some(ColliderState::Solid)
+                ) {
                     continue;
                 }
 
                 let body2 = bodies
                     .get(collider2.owner)
                     .expect("Collider without a body");
+                if !body2.enabled {
+                    continue;
+                }
 
+                if let Some(stop) = solver_stop(
+                    collider1,
+                    body1.position,
+                    Vec2::unit_x(),
+                    move_x,
+                    collider2,
+                    body2.position,
+                    N::from_f64(0.001),
+                ) {
+                    move_x = if move_x > N::zero() {
+                        move_x.partial_min(stop)
+                    } else {
+                        move_x.partial_max(stop)
+                    };
+                }
                 if is_penetrating(
                     collider1,
-                    body1.position + Vec2::new(move_x, to_fp(0.)),
+                    body1.position + Vec2::new(move_x, N::zero()),
                     collider2,
                     body2.position,
-                    to_fp(0.001),
+                    N::from_f64(0.001),
                 ) {
-                    if body1.velocity.x() > 0. {
-                        move_x = move_x.min(
+                    let reach = solver_reach(collider1, body1.position, collider2, body2.position);
+                    if body1.velocity.x() > N::zero() {
+                        move_x = move_x.partial_min(
                             body2.position.x() - collider1.offset.x() + collider2.offset.x()
-                                - collider2.shape.half_exts.x()
-                                - collider1.shape.half_exts.x()
+                                - reach.x()
                                 - body1.position.x(),
                         );
                     } else {
-                        move_x = move_x.max(
+                        move_x = move_x.partial_max(
                             body2.position.x() - collider1.offset.x()
                                 + collider2.offset.x()
-                                + collider2.shape.half_exts.x()
-                                + collider1.shape.half_exts.x()
+                                + reach.x()
                                 - body1.position.x(),
                         );
                     }
@@ -275,17 +762,18 @@ fn step_x<T>(bodies: &mut BodySet, colliders: &mut ColliderSet<T>, body_handles:
     }
 }
 
-fn step_y<T>(
-    bodies: &mut BodySet,
-    colliders: &mut ColliderSet<T>,
-    collision_graph: &mut CollisionGraph,
+fn step_y<T, N: Scalar>(
+    bodies: &mut BodySet<N>,
+    colliders: &mut ColliderSet<T, N>,
+    layers: &LayerMatrix,
+    collision_graph: &mut CollisionGraph<N>,
     body_handles: &[BodyHandle],
 ) {
     for body1_handle in body_handles {
         let body1 = bodies.get(*body1_handle).expect("Collider without a body");
         let mut move_y = body1.movement.y();
 
-        if let BodyStatus::Static = body1.status {
+        if !body1.is_awake() {
             continue;
         }
 
@@ -296,43 +784,58 @@ fn step_y<T>(
 
             // TODO: Broadphase scan just the neighbours
             for (coll2_handle, collider2) in colliders.iter() {
-                let coll2_handle = coll2_handle;
                 // no collider colliding with itself
                 if *coll1_handle == coll2_handle {
                     continue;
                 }
 
-                if !can_collide(body1, collider1, collider2) {
+                let Some(state) = pair_state(layers, body1, collider1, collider2) else {
                     continue;
-                }
+                };
 
                 let body2 = bodies
                     .get(collider2.owner)
                     .expect("Collider without a body");
+                if !body2.enabled {
+                    continue;
+                }
 
-                if let (ColliderState::Solid, ColliderState::Solid) =
-                    (collider1.state, collider2.state)
-                {
+                if let ColliderState::Solid = state {
+                    if let Some(stop) = solver_stop(
+                        collider1,
+                        body1.position,
+                        Vec2::unit_y(),
+                        move_y,
+                        collider2,
+                        body2.position,
+                        N::from_f64(0.001),
+                    ) {
+                        move_y = if move_y > N::zero() {
+                            move_y.partial_min(stop)
+                        } else {
+                            move_y.partial_max(stop)
+                        };
+                    }
                     if is_penetrating(
                         collider1,
-                        body1.position + Vec2::new(to_fp(0.), move_y),
+                        body1.position + Vec2::new(N::zero(), move_y),
                         collider2,
                         body2.position,
-                        to_fp(0.001),
+                        N::from_f64(0.001),
                     ) {
-                        if body1.velocity.y() > 0. {
-                            move_y = move_y.min(
+                        let reach =
+                            solver_reach(collider1, body1.position, collider2, body2.position);
+                        if body1.velocity.y() > N::zero() {
+                            move_y = move_y.partial_min(
                                 body2.position.y() - collider1.offset.y() + collider2.offset.y()
-                                    - collider2.shape.half_exts.y()
-                                    - collider1.shape.half_exts.y()
+                                    - reach.y()
                                     - body1.position.y(),
                             );
                         } else {
-                            move_y = move_y.max(
+                            move_y = move_y.partial_max(
                                 body2.position.y() - collider1.offset.y()
                                     + collider2.offset.y()
-                                    + collider2.shape.half_exts.y()
-                                    + collider1.shape.half_exts.y()
+                                    + reach.y()
                                     - body1.position.y(),
                             );
                         }
@@ -350,29 +853,152 @@ fn step_y<T>(
     }
 }
 
-fn can_collide<T>(body1: &Body, collider1: &Collider<T>, collider2: &Collider<T>) -> bool {
-    let category_mismatch = ((collider1.category_bits & collider2.mask_bits) == 0)
-        || ((collider2.category_bits & collider1.mask_bits) == 0);
-    // only colliders with matching masks can collide
-    if category_mismatch {
-        return false;
+// `Solid` if the pair blocks each other, `Sensor` if it only overlaps and `None` if it doesn't interact at all.
+fn pair_state<T, N: Scalar>(
+    layers: &LayerMatrix,
+    body1: &Body<N>,
+    collider1: &Collider<T, N>,
+    collider2: &Collider<T, N>,
+) -> Option<ColliderState> {
+    if !collider1.enabled || !collider2.enabled {
+        return None;
     }
-
     // don't collide with same body if it's disabled
     if collider1.owner == collider2.owner && !body1.self_collide {
-        return false;
+        return None;
+    }
+
+    let interaction = match (collider1.layer, collider2.layer) {
+        (Some(layer1), Some(layer2)) => layers.interaction(layer1, layer2),
+        // only colliders with matching masks can collide
+        _ if ((collider1.category_bits & collider2.mask_bits) == 0)
+            || ((collider2.category_bits & collider1.mask_bits) == 0) =>
+        {
+            LayerInteraction::Ignore
+        }
+        // solid against everything but the categories in either overlap mask
+        _ if ((collider1.category_bits & collider2.overlap_bits) != 0)
+            || ((collider2.category_bits & collider1.overlap_bits) != 0) =>
+        {
+            LayerInteraction::Overlap
+        }
+        _ => LayerInteraction::Collide,
+    };
+    match (interaction, collider1.state, collider2.state) {
+        (LayerInteraction::Ignore, _, _) => None,
+        (LayerInteraction::Collide, ColliderState::Solid, ColliderState::Solid) => {
+            Some(ColliderState::Solid)
+        }
+        _ => Some(ColliderState::Sensor),
     }
-    true
 }
 
-fn describe_collisions<T: Copy>(
-    bodies: &BodySet,
-    colliders: &ColliderSet<T>,
-    collision_graph: &mut CollisionGraph,
+// Wakes the sleeping bodies touching a static body moved by the user, before or after the move.
+fn wake_touching<T, N: Scalar>(
+    handle: BodyHandle,
+    bodies: &mut BodySet<N>,
+    colliders: &ColliderSet<T, N>,
+    collision_graph: &CollisionGraph<N>,
+) {
+    let moved = &bodies[handle];
+    let mut touching: Vec<BodyHandle> = moved
+        .colliders
+        .iter()
+        .flat_map(|coll_handle| collision_graph.edges(*coll_handle))
+        .map(|(other, _)| colliders[other].owner)
+        .collect();
+    for (other_handle, other) in bodies.iter() {
+        if !other.sleeping {
+            continue;
+        }
+        let overlaps = other.colliders.iter().any(|other_coll| {
+            moved.colliders.iter().any(|coll| {
+                is_colliding(
+                    &colliders[*coll],
+                    moved.position,
+                    &colliders[*other_coll],
+                    other.position,
+                )
+            })
+        });
+        if overlaps {
+            touching.push(other_handle);
+        }
+    }
+    for body_handle in touching {
+        bodies[body_handle].wake_up();
+    }
+}
+
+fn current_interaction<T, N: Scalar>(
+    layers: &LayerMatrix,
+    body1: &Body<N>,
+    collider1: &Collider<T, N>,
+    body2: &Body<N>,
+    collider2: &Collider<T, N>,
+) -> Option<Interaction<N>> {
+    if !body1.enabled || !body2.enabled {
+        return None;
+    }
+    let (position1, position2) = (body1.position, body2.position);
+    match pair_state(layers, body1, collider1, collider2) {
+        Some(ColliderState::Solid) => collision_contact(collider1, position1, collider2, position2)
+            .map(|contact| Interaction::Collision(CollisionInfo::from(&contact))),
+        Some(ColliderState::Sensor) if is_colliding(collider1, position1, collider2, position2) => {
+            Some(Interaction::Overlap)
+        }
+        _ => None,
+    }
+}
+
+// Skips the events either of the colliders opted out of.
+fn push_event<T, N>(
+    events: &mut Vec<ContactEvent<T>>,
+    collider1: &Collider<T, N>,
+    collider2: &Collider<T, N>,
+    event: ContactEvent<T>,
+) {
+    if collider1.events.allows(&event) && collider2.events.allows(&event) {
+        events.push(event);
+    }
+}
+
+// Started and ended events for the change of a pair's interaction, returns whether a new one started.
+fn push_interaction_events<T: Copy, N: Scalar>(
+    events: &mut Vec<ContactEvent<T>>,
+    (handle1, collider1): (ColliderHandle, &Collider<T, N>),
+    (handle2, collider2): (ColliderHandle, &Collider<T, N>),
+    previous: Option<&Interaction<N>>,
+    current: Option<&Interaction<N>>,
+) -> bool {
+    let event = |interaction| {
+        ContactEvent::with_interaction(handle1, collider1, handle2, collider2, interaction)
+    };
+    let mut push = |event| push_event(events, collider1, collider2, event);
+    match (previous, current) {
+        (None, Some(current)) => push(event(current)),
+        (Some(previous), None) => push(event(previous).into_finished()),
+        // e.g. one of the colliders became a sensor
+        (Some(previous), Some(current)) if previous.is_overlap() != current.is_overlap() => {
+            push(event(previous).into_finished());
+            push(event(current));
+        }
+        _ => return false,
+    }
+    current.is_some()
+}
+
+fn describe_collisions<T: Copy, N: Scalar>(
+    bodies: &mut BodySet<N>,
+    colliders: &ColliderSet<T, N>,
+    layers: &LayerMatrix,
+    collision_graph: &mut CollisionGraph<N>,
     events: &mut Vec<ContactEvent<T>>,
+    step: u64,
 ) {
     // TODO: Don't reallocate
     let mut removed_edges = vec![];
+    let mut woken = vec![];
 
     // collision event and contact information
     for edge_id in collision_graph.src.edge_indices() {
@@ -382,49 +1008,43 @@ fn describe_collisions<T: Copy>(
         let collider1 = &colliders[handle1];
         let collider2 = &colliders[handle2];
 
-        let previous_interaction = collision_graph.src.edge_weight_mut(edge_id).unwrap();
+        let edge = collision_graph.src.edge_weight_mut(edge_id).unwrap();
 
-        let position1 = bodies
+        let body1 = bodies
             .get(collider1.owner)
-            .expect("Collider without a body")
-            .position;
-        let position2 = bodies
+            .expect("Collider without a body");
+        let body2 = bodies
             .get(collider2.owner)
-            .expect("Collider without a body")
-            .position;
-
-        let current_interaction = {
-            use ColliderState::Solid;
-            if let (Solid, Solid) = (collider1.state, collider2.state) {
-                if let Some(manifold) =
-                    collision_manifold(collider1, position1, collider2, position2)
-                {
-                    // manifolds.push((collider1.owner.0, collider2.owner.0, manifold));
-                    Some(Interaction::Collision(CollisionInfo::from(
-                        manifold.best_contact(),
-                    )))
-                } else {
-                    None
+            .expect("Collider without a body");
+        // nothing moved, so the interaction stays the same
+        if !body1.is_awake() && !body2.is_awake() {
+            continue;
+        }
+        let current_interaction = current_interaction(layers, body1, collider1, body2, collider2);
+
+        // touched by a moving body
+        if current_interaction.is_some() {
+            for (sleeper, other) in [(collider1.owner, body2), (collider2.owner, body1)] {
+                if bodies[sleeper].sleeping && other.is_awake() && other.movement != Vec2::zero() {
+                    woken.push(sleeper);
                 }
-            } else if is_colliding(collider1, position1, collider2, position2) {
-                Some(Interaction::Overlap)
-            } else {
-                None
             }
-        };
+        }
 
-        if current_interaction.is_some() && previous_interaction.is_none() {
-            events.push(ContactEvent::new(handle1, collider1, handle2, collider2));
+        let started = push_interaction_events(
+            events,
+            (handle1, collider1),
+            (handle2, collider2),
+            edge.interaction.as_ref(),
+            current_interaction.as_ref(),
+        );
+        if started {
+            edge.started = step;
         }
         if current_interaction.is_none() {
             removed_edges.push((node1_id, node2_id));
-            if previous_interaction.is_some() {
-                events.push(
-                    ContactEvent::new(handle1, collider1, handle2, collider2).into_finished(),
-                );
-            }
         }
-        *previous_interaction = current_interaction;
+        edge.interaction = current_interaction;
     }
 
     removed_edges.into_iter().for_each(|(node1_id, node2_id)| {
@@ -440,4 +1060,910 @@ fn describe_collisions<T: Copy>(
             );
         }
     });
+    for handle in woken {
+        bodies[handle].wake_up();
+    }
+}
+
+// Deduplicated owners of the colliders overlapping the collider.
+fn overlapping_bodies<T, N: Scalar>(
+    handle: ColliderHandle,
+    colliders: &ColliderSet<T, N>,
+    collision_graph: &CollisionGraph<N>,
+) -> Result<Vec<BodyHandle>, PhysicsError> {
+    let mut bodies = Vec::new();
+    for (other, interaction) in collision_graph.try_edges(handle)? {
+        let owner = colliders[other].owner;
+        if interaction.is_overlap() && !bodies.contains(&owner) {
+            bodies.push(owner);
+        }
+    }
+    Ok(bodies)
+}
+
+// Compares the bodies inside every sensor with the last step, removed sensors lose all of them.
+fn update_occupants<T: Copy, N: Scalar>(
+    colliders: &ColliderSet<T, N>,
+    collision_graph: &CollisionGraph<N>,
+    occupants: &mut Vec<(ColliderHandle, T, Vec<BodyHandle>)>,
+    events: &mut Vec<SensorEvent<T>>,
+) {
+    let previous: FxHashMap<ColliderHandle, &[BodyHandle]> = occupants
+        .iter()
+        .map(|(handle, _, bodies)| (*handle, bodies.as_slice()))
+        .collect();
+    let mut current = Vec::new();
+    for (handle, collider) in colliders.iter() {
+        if !matches!(collider.state, ColliderState::Sensor) {
+            continue;
+        }
+        let bodies = overlapping_bodies(handle, colliders, collision_graph).unwrap_or_default();
+        let before = previous.get(&handle).copied().unwrap_or_default();
+        let tag = collider.user_tag;
+        for body in bodies.iter().filter(|body| !before.contains(body)) {
+            events.push(SensorEvent::Entered(handle, *body, tag));
+        }
+        for body in before.iter().filter(|body| !bodies.contains(body)) {
+            events.push(SensorEvent::Exited(handle, *body, tag));
+        }
+        current.push((handle, tag, bodies));
+    }
+    let sensors: FxHashSet<ColliderHandle> = current.iter().map(|(handle, ..)| *handle).collect();
+    for (handle, tag, before) in occupants.iter() {
+        if !sensors.contains(handle) {
+            for body in before {
+                events.push(SensorEvent::Exited(*handle, *body, *tag));
+            }
+        }
+    }
+    *occupants = current;
+}
+
+// Compares the colliding pairs of bodies with the last step, each pair ordered by its handles.
+fn update_body_contacts<T, N: Scalar>(
+    colliders: &ColliderSet<T, N>,
+    collision_graph: &CollisionGraph<N>,
+    contacts: &mut Vec<(BodyHandle, BodyHandle, Vec2<N>)>,
+    events: &mut Vec<BodyContactEvent<N>>,
+) {
+    let mut touching = Vec::new();
+    for edge in collision_graph.src.edge_references() {
+        let Some(Interaction::Collision(info)) = &edge.weight().interaction else {
+            continue;
+        };
+        let (handle1, handle2) = (
+            collision_graph.src[edge.source()],
+            collision_graph.src[edge.target()],
+        );
+        let body1 = colliders[handle1].owner;
+        let body2 = colliders[handle2].owner;
+        let handles = (handle1.min(handle2), handle1.max(handle2));
+        match body1.cmp(&body2) {
+            Ordering::Less => touching.push((body1, body2, handles, info.normal)),
+            Ordering::Greater => touching.push((body2, body1, handles, -info.normal)),
+            Ordering::Equal => {}
+        }
+    }
+    // several collider pairs of two bodies can touch, the normal of the pair with the lowest handles is kept
+    // so it doesn't depend on the order of the edges, which changes with insertions and removals
+    touching.sort_by_key(|(body1, body2, handles, _)| (*body1, *body2, *handles));
+    touching.dedup_by_key(|(body1, body2, ..)| (*body1, *body2));
+    let current: Vec<(BodyHandle, BodyHandle, Vec2<N>)> = touching
+        .into_iter()
+        .map(|(body1, body2, _, normal)| (body1, body2, normal))
+        .collect();
+    let pairs: FxHashSet<(BodyHandle, BodyHandle)> =
+        current.iter().map(|(a, b, _)| (*a, *b)).collect();
+    let previous: FxHashSet<(BodyHandle, BodyHandle)> =
+        contacts.iter().map(|(a, b, _)| (*a, *b)).collect();
+    for (body1, body2, normal) in current.iter() {
+        if !previous.contains(&(*body1, *body2)) {
+            events.push(BodyContactEvent::Started(*body1, *body2, *normal));
+        }
+    }
+    for (body1, body2, normal) in contacts.iter() {
+        if !pairs.contains(&(*body1, *body2)) {
+            events.push(BodyContactEvent::Ended(*body1, *body2, *normal));
+        }
+    }
+    *contacts = current;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{BodyDesc, ColliderDesc};
+    use crate::test_utils::{add_static, add_with_collider, dt, floor, square};
+    use crate::{to_fp, ColliderEvents, World, AABB};
+
+    #[test]
+    fn pairs_decide_between_collision_and_overlap() {
+        const TERRAIN: u32 = 1;
+        const PLAYER: u32 = 2;
+        let mut world = PhysicsWorld::<&str>::new();
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+        let floor = bodies.insert(BodyDesc::new().make_static().build());
+        colliders.insert(
+            ColliderDesc::new(
+                AABB {
+                    half_exts: Vec2::from(100, 8),
+                },
+                "floor",
+            )
+            .with_category(TERRAIN)
+            .build(floor),
+            &mut bodies,
+            &mut world,
+        );
+        let player = ColliderDesc::new(
+            AABB {
+                half_exts: Vec2::from(8, 8),
+            },
+            "player",
+        )
+        .with_category(PLAYER)
+        .with_overlap_mask(PLAYER);
+        let mut players = vec![];
+        for (x, velocity_x) in [(-30, 120), (30, -120)] {
+            let body = bodies.insert(
+                BodyDesc::new()
+                    .with_position(Vec2::from(x, -20))
+                    .with_velocity(Vec2::from(velocity_x, 120))
+                    .build(),
+            );
+            let handle = colliders
+                .insert(player.clone().build(body), &mut bodies, &mut world)
+                .unwrap();
+            players.push((body, handle));
+        }
+
+        let mut overlapped = false;
+        for _ in 0..20 {
+            world.step(to_fp(1. / 60.), &mut bodies, &mut colliders);
+            overlapped |= world.events().iter().any(|event| {
+                matches!(
+                    event,
+                    ContactEvent::OverlapStarted(_, _, "player", "player")
+                )
+            });
+            if let Some((_, interaction)) = world
+                .interactions_of(players[0].1)
+                .find(|(h, _)| *h == players[1].1)
+            {
+                assert!(interaction.is_overlap());
+            }
+        }
+        assert!(overlapped);
+        // passed through each other but both stand on the floor
+        let (left, right) = (&bodies[players[0].0], &bodies[players[1].0]);
+        assert!(left.position.x() > right.position.x());
+        assert_eq!(left.position.y(), to_fp(-16));
+        assert_eq!(right.position.y(), to_fp(-16));
+        assert!(world
+            .collisions_of(players[0].1)
+            .any(|(handle, _)| colliders[handle].user_tag == "floor"));
+    }
+
+    #[test]
+    fn events_keep_their_order_when_the_state_changes() {
+        let mut world = PhysicsWorld::<&str>::new();
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+        let square = AABB {
+            half_exts: Vec2::from(8, 8),
+        };
+        let water = bodies.insert(BodyDesc::new().make_static().build());
+        let water = colliders
+            .insert(
+                ColliderDesc::new(square, "water").sensor().build(water),
+                &mut bodies,
+                &mut world,
+            )
+            .unwrap();
+        let diver = bodies.insert(BodyDesc::new().with_position(Vec2::from(4, 4)).build());
+        let diver = colliders
+            .insert(
+                ColliderDesc::new(square, "diver").build(diver),
+                &mut bodies,
+                &mut world,
+            )
+            .unwrap();
+        assert!(water < diver);
+
+        let mut events = vec![];
+        for state in [ColliderState::Solid, ColliderState::Sensor] {
+            for _ in 0..2 {
+                world.step(to_fp(1. / 60.), &mut bodies, &mut colliders);
+                events.extend_from_slice(world.events());
+            }
+            world
+                .set_collider_state(water, state, &mut bodies, &mut colliders)
+                .unwrap();
+        }
+        world.step(to_fp(1. / 60.), &mut bodies, &mut colliders);
+        events.extend_from_slice(world.events());
+
+        let collisions: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                ContactEvent::CollisionStarted(h1, h2, ..)
+                | ContactEvent::CollisionEnded(h1, h2, ..) => Some((*h1, *h2)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(collisions, [(water, diver), (water, diver)]);
+        // overlaps of the sensor put the solid diver first
+        assert!(matches!(
+            events[0],
+            ContactEvent::OverlapStarted(h1, h2, ..) if (h1, h2) == (diver, water)
+        ));
+    }
+
+    #[test]
+    fn bodies_stay_awake_by_default() {
+        let mut world = PhysicsWorld::<&str>::new();
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+        let floor = bodies.insert(BodyDesc::new().make_static().build());
+        let floor_collider = colliders
+            .insert(
+                ColliderDesc::new(
+                    AABB {
+                        half_exts: Vec2::from(100, 8),
+                    },
+                    "floor",
+                )
+                .build(floor),
+                &mut bodies,
+                &mut world,
+            )
+            .unwrap();
+        let crate_body = bodies.insert(BodyDesc::new().with_position(Vec2::from(0, -16)).build());
+        colliders.insert(
+            ColliderDesc::new(
+                AABB {
+                    half_exts: Vec2::from(8, 8),
+                },
+                "crate",
+            )
+            .build(crate_body),
+            &mut bodies,
+            &mut world,
+        );
+
+        for _ in 0..120 {
+            world.step(to_fp(1. / 60.), &mut bodies, &mut colliders);
+        }
+        assert!(!bodies[crate_body].is_sleeping());
+        assert_eq!(world.collisions_of(floor_collider).count(), 1);
+    }
+
+    #[test]
+    fn bodies_fall_asleep_and_wake_up() {
+        let mut world = PhysicsWorld::<&str>::new();
+        world.sleep_steps = 10;
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+        let square = AABB {
+            half_exts: Vec2::from(8, 8),
+        };
+        let floor = bodies.insert(BodyDesc::new().make_static().build());
+        let floor_collider = colliders
+            .insert(
+                ColliderDesc::new(
+                    AABB {
+                        half_exts: Vec2::from(100, 8),
+                    },
+                    "floor",
+                )
+                .build(floor),
+                &mut bodies,
+                &mut world,
+            )
+            .unwrap();
+        let crate_body = bodies.insert(BodyDesc::new().with_position(Vec2::from(0, -16)).build());
+        colliders.insert(
+            ColliderDesc::new(square, "crate").build(crate_body),
+            &mut bodies,
+            &mut world,
+        );
+        let player = bodies.insert(
+            BodyDesc::new()
+                .with_position(Vec2::from(-60, -16))
+                .can_sleep(false)
+                .build(),
+        );
+        colliders.insert(
+            ColliderDesc::new(square, "player").build(player),
+            &mut bodies,
+            &mut world,
+        );
+        let dt = to_fp(1. / 60.);
+
+        for _ in 0..10 {
+            world.step(dt, &mut bodies, &mut colliders);
+        }
+        assert!(bodies[crate_body].is_sleeping());
+        assert!(!bodies[player].is_sleeping());
+        // resting contacts persist without any events
+        world.step(dt, &mut bodies, &mut colliders);
+        assert!(world.events().is_empty());
+        assert_eq!(world.collisions_of(floor_collider).count(), 2);
+
+        // touched by the walking player
+        bodies[player].velocity = Vec2::from(600, 0);
+        for _ in 0..6 {
+            world.step(dt, &mut bodies, &mut colliders);
+        }
+        assert!(!bodies[crate_body].is_sleeping());
+        assert_eq!(bodies[player].position.x(), to_fp(-16));
+        bodies[player].velocity = Vec2::zero();
+
+        for _ in 0..10 {
+            world.step(dt, &mut bodies, &mut colliders);
+        }
+        assert!(bodies[crate_body].is_sleeping());
+        // moved through the API
+        *bodies[crate_body].position.x_mut() += to_fp(20);
+        world.step(dt, &mut bodies, &mut colliders);
+        assert!(!bodies[crate_body].is_sleeping());
+
+        for _ in 0..10 {
+            world.step(dt, &mut bodies, &mut colliders);
+        }
+        assert!(bodies[crate_body].is_sleeping());
+        // the floor it rested on disappeared
+        world.remove_collider(floor_collider, &mut bodies, &mut colliders);
+        assert!(!bodies[crate_body].is_sleeping());
+        world.step(dt, &mut bodies, &mut colliders);
+        assert!(world.events().iter().any(|event| matches!(
+            event,
+            ContactEvent::CollisionEnded(_, _, "floor", "crate")
+                | ContactEvent::CollisionEnded(_, _, "crate", "floor")
+        )));
+    }
+
+    #[test]
+    fn colliders_change_at_runtime() {
+        let mut world = World::<&str>::new();
+        let (floor, floor_collider) = add_static(&mut world, Vec2::zero(), floor("floor"));
+        let (player, player_collider) = add_with_collider(
+            &mut world,
+            BodyDesc::new()
+                .with_position(Vec2::from(0, -16))
+                .self_collision(false),
+            ColliderDesc::new(square(), "player"),
+        );
+        world.step(dt());
+        world.step(dt());
+        assert_eq!(world.physics.collisions_of(player_collider).count(), 1);
+
+        world
+            .set_collider_state(player_collider, ColliderState::Sensor)
+            .unwrap();
+        world.step(dt());
+        assert!(matches!(
+            world.events(),
+            [
+                ContactEvent::CollisionEnded(..),
+                ContactEvent::OverlapStarted(_, _, "floor", "player")
+            ]
+        ));
+        world
+            .set_collider_state(player_collider, ColliderState::Solid)
+            .unwrap();
+        world.step(dt());
+        assert!(matches!(
+            world.events(),
+            [
+                ContactEvent::OverlapEnded(..),
+                ContactEvent::CollisionStarted(..)
+            ]
+        ));
+
+        // lifted off the floor
+        world
+            .set_collider_offset(player_collider, Vec2::from(0, -10))
+            .unwrap();
+        world.step(dt());
+        assert!(matches!(world.events(), [ContactEvent::CollisionEnded(..)]));
+        // grown back into it
+        world
+            .set_collider_shape(
+                player_collider,
+                AABB {
+                    half_exts: Vec2::from(8, 20),
+                },
+            )
+            .unwrap();
+        world.step(dt());
+        assert!(matches!(
+            world.events(),
+            [ContactEvent::CollisionStarted(..)]
+        ));
+
+        // the floor joins the player's body, which doesn't collide with itself
+        world.set_collider_owner(floor_collider, player).unwrap();
+        assert!(world.body(floor).unwrap().colliders.is_empty());
+        assert_eq!(world.body(player).unwrap().colliders.len(), 2);
+        world.step(dt());
+        assert!(matches!(world.events(), [ContactEvent::CollisionEnded(..)]));
+        let chain = crate::SegmentChain::new(vec![Vec2::zero(), Vec2::from(10, 0)]).unwrap();
+        assert_eq!(
+            world.set_collider_shape(floor_collider, chain),
+            Err(PhysicsError::ChainOnKinematicBody(player))
+        );
+    }
+
+    #[test]
+    fn teleports_update_contacts() {
+        let mut world = World::<&str>::new();
+        let (floor, _) = add_static(&mut world, Vec2::zero(), floor("floor"));
+        add_static(
+            &mut world,
+            Vec2::from(200, 0),
+            ColliderDesc::new(square(), "coin").sensor(),
+        );
+        let (player, player_collider) = add_with_collider(
+            &mut world,
+            BodyDesc::new().with_position(Vec2::from(0, -16)),
+            ColliderDesc::new(square(), "player"),
+        );
+        world.step(dt());
+        world.step(dt());
+        assert_eq!(world.physics.collisions_of(player_collider).count(), 1);
+
+        // kept contacts are left for the next step
+        world
+            .teleport(player, Vec2::from(0, -100), TeleportContacts::Keep)
+            .unwrap();
+        assert_eq!(world.physics.collisions_of(player_collider).count(), 1);
+        world
+            .teleport(player, Vec2::from(0, -16), TeleportContacts::Keep)
+            .unwrap();
+        world.step(dt());
+        assert!(world.events().is_empty());
+
+        // onto the coin, away from the floor
+        world
+            .teleport(player, Vec2::from(200, 0), TeleportContacts::Reevaluate)
+            .unwrap();
+        assert_eq!(world.physics.collisions_of(player_collider).count(), 0);
+        assert_eq!(world.physics.overlaps_of(player_collider).count(), 1);
+        assert_eq!(world.body(player).unwrap().position, Vec2::from(200, 0));
+        world.step(dt());
+        assert!(matches!(
+            world.events(),
+            [
+                ContactEvent::CollisionEnded(..),
+                ContactEvent::OverlapStarted(..)
+            ]
+        ));
+
+        world
+            .teleport(player, Vec2::from(200, 0), TeleportContacts::Clear)
+            .unwrap();
+        assert_eq!(world.physics.interactions_of(player_collider).count(), 0);
+        // still holding, so started again by the next step
+        world.step(dt());
+        assert!(matches!(
+            world.events(),
+            [
+                ContactEvent::OverlapEnded(..),
+                ContactEvent::OverlapStarted(..)
+            ]
+        ));
+        assert_eq!(
+            world.teleport(floor, Vec2::zero(), TeleportContacts::Keep),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn disabled_bodies_and_colliders_keep_their_handles() {
+        let mut world = World::<&str>::new();
+        let (_, floor_collider) = add_static(&mut world, Vec2::zero(), floor("floor"));
+        let (bullet, bullet_collider) = add_with_collider(
+            &mut world,
+            BodyDesc::new().with_position(Vec2::from(0, -16)),
+            ColliderDesc::new(square(), "bullet"),
+        );
+        world.step(dt());
+        world.step(dt());
+        assert_eq!(world.physics.collisions_of(floor_collider).count(), 1);
+
+        world.set_body_enabled(bullet, false).unwrap();
+        assert!(!world.body(bullet).unwrap().is_enabled());
+        world.body_mut(bullet).unwrap().velocity = Vec2::from(0, 600);
+        world.step(dt());
+        assert!(matches!(world.events(), [ContactEvent::CollisionEnded(..)]));
+        world.step(dt());
+        assert!(world.events().is_empty());
+        // neither moved nor found by queries
+        assert_eq!(world.body(bullet).unwrap().position.y(), to_fp(-16));
+        assert_eq!(
+            world
+                .overlap_test(Vec2::from(0, -16), Vec2::from(4, 4), u32::MAX)
+                .count(),
+            0
+        );
+
+        world.body_mut(bullet).unwrap().velocity = Vec2::zero();
+        world.set_body_enabled(bullet, true).unwrap();
+        world.step(dt());
+        assert!(matches!(
+            world.events(),
+            [ContactEvent::CollisionStarted(..)]
+        ));
+
+        world.set_collider_enabled(floor_collider, false).unwrap();
+        world.body_mut(bullet).unwrap().velocity = Vec2::from(0, 600);
+        world.step(dt());
+        assert!(matches!(world.events(), [ContactEvent::CollisionEnded(..)]));
+        world.step(dt());
+        // the bullet fell through the disabled floor
+        assert!(world.body(bullet).unwrap().position.y() > to_fp(-16));
+        assert_eq!(world.physics.interactions_of(bullet_collider).count(), 0);
+    }
+
+    #[test]
+    fn sensors_track_the_bodies_inside() {
+        let mut world = World::<&str>::new();
+        let water = ColliderDesc::new(
+            AABB {
+                half_exts: Vec2::from(20, 20),
+            },
+            "water",
+        );
+        let (_, water) = add_static(&mut world, Vec2::zero(), water.sensor());
+        // two colliders side by side
+        let swimmer = world.add_body(
+            BodyDesc::new()
+                .with_position(Vec2::from(-60, 0))
+                .with_velocity(Vec2::from(600, 0))
+                .self_collision(false),
+        );
+        for (x, tag) in [(-4, "front"), (-12, "back")] {
+            world
+                .add_collider(
+                    ColliderDesc::new(
+                        AABB {
+                            half_exts: Vec2::from(4, 4),
+                        },
+                        tag,
+                    )
+                    .with_offset(Vec2::from(x + 8, 0)),
+                    swimmer,
+                )
+                .unwrap();
+        }
+
+        let mut sensor_events = vec![];
+        for _ in 0..6 {
+            world.step(dt());
+            sensor_events.extend_from_slice(world.sensor_events());
+        }
+        assert_eq!(world.physics.overlaps_of(water).count(), 2);
+        assert_eq!(
+            world.occupants_of(water).unwrap().collect::<Vec<_>>(),
+            vec![swimmer]
+        );
+        assert_eq!(
+            sensor_events,
+            vec![SensorEvent::Entered(water, swimmer, "water")]
+        );
+
+        world.remove_collider(water).unwrap();
+        world.step(dt());
+        assert_eq!(
+            world.sensor_events(),
+            [SensorEvent::Exited(water, swimmer, "water")]
+        );
+        assert_eq!(
+            world.occupants_of(water).err(),
+            Some(PhysicsError::ColliderNotInGraph(water))
+        );
+    }
+
+    #[test]
+    fn body_pairs_collide_once() {
+        let mut world = World::<&str>::new();
+        let (floor, _) = add_static(&mut world, Vec2::zero(), floor("floor"));
+        let walker = world.add_body(
+            BodyDesc::new()
+                .with_position(Vec2::from(0, -40))
+                .with_velocity(Vec2::from(0, 600))
+                .self_collision(false),
+        );
+        for x in [-6, 6] {
+            world
+                .add_collider(
+                    ColliderDesc::new(
+                        AABB {
+                            half_exts: Vec2::from(4, 8),
+                        },
+                        "foot",
+                    )
+                    .with_offset(Vec2::from(x, 0)),
+                    walker,
+                )
+                .unwrap();
+        }
+
+        let mut body_events = vec![];
+        for _ in 0..4 {
+            world.step(dt());
+            body_events.extend_from_slice(world.body_events());
+        }
+        assert_eq!(
+            world
+                .events()
+                .iter()
+                .filter(|event| matches!(event, ContactEvent::CollisionStarted(..)))
+                .count(),
+            2
+        );
+        assert_eq!(
+            body_events,
+            vec![BodyContactEvent::Started(floor, walker, -Vec2::unit_y())]
+        );
+
+        world.remove_body(floor).unwrap();
+        world.step(dt());
+        assert_eq!(
+            world.body_events(),
+            [BodyContactEvent::Ended(floor, walker, -Vec2::unit_y())]
+        );
+    }
+
+    // a box ending up in the corner of a static body with a floor and a wall collider, the body events
+    // until the corner is removed, the collider of the box is inserted either first or last
+    fn land_in_corner(position: Vec2, velocity: Vec2, box_first: bool) -> Vec<BodyContactEvent> {
+        let mut world = World::<&str>::new();
+        let corner = world.add_body(BodyDesc::new().make_static());
+        let moving = world.add_body(
+            BodyDesc::new()
+                .with_position(position)
+                .with_velocity(velocity),
+        );
+        let wall = ColliderDesc::new(
+            AABB {
+                half_exts: Vec2::from(8, 50),
+            },
+            "wall",
+        )
+        .with_offset(Vec2::from(40, -50));
+        let mut descs = vec![
+            (floor("floor"), corner),
+            (wall, corner),
+            (ColliderDesc::new(square(), "box"), moving),
+        ];
+        if box_first {
+            descs.rotate_right(1);
+        }
+        for (desc, body) in descs {
+            world.add_collider(desc, body).unwrap();
+        }
+
+        let mut body_events = vec![];
+        for _ in 0..10 {
+            world.step(dt());
+            body_events.extend_from_slice(world.body_events());
+        }
+        world.remove_body(corner).unwrap();
+        world.step(dt());
+        body_events.extend_from_slice(world.body_events());
+        body_events
+    }
+
+    #[test]
+    fn body_contacts_use_the_lowest_collider_pair() {
+        // falls along the wall, so the pair with the wall is found before the one with the floor
+        let body_events = land_in_corner(Vec2::from(24, -60), Vec2::from(0, 600), false);
+        assert!(matches!(
+            body_events.as_slice(),
+            [
+                BodyContactEvent::Started(_, _, started),
+                BodyContactEvent::Ended(_, _, ended),
+            ] if *started == -Vec2::unit_x() && *ended == -Vec2::unit_y()
+        ));
+    }
+
+    #[test]
+    fn body_contacts_dont_depend_on_the_history() {
+        let ended = |position, velocity, box_first| {
+            let body_events = land_in_corner(position, velocity, box_first);
+            match body_events.last() {
+                Some(BodyContactEvent::Ended(_, _, normal)) => *normal,
+                event => panic!("{:?}", event),
+            }
+        };
+        // slides along the floor or falls along the wall into the same corner
+        let slide = (Vec2::from(-40, -16), Vec2::from(600, 0));
+        let fall = (Vec2::from(24, -60), Vec2::from(0, 600));
+        let normal = ended(slide.0, slide.1, false);
+        assert_eq!(ended(fall.0, fall.1, false), normal);
+        assert_eq!(ended(slide.0, slide.1, true), normal);
+        assert_eq!(ended(fall.0, fall.1, true), normal);
+    }
+
+    #[test]
+    fn interactions_know_how_long_they_last() {
+        let mut world = World::<&str>::new();
+        add_static(&mut world, Vec2::zero(), floor("floor"));
+        let (plate, plate_collider) = add_with_collider(
+            &mut world,
+            BodyDesc::new().with_position(Vec2::from(0, -16)),
+            ColliderDesc::new(square(), "plate"),
+        );
+        world.step(dt());
+        assert_eq!(world.steps(), 1);
+        let durations = |world: &World<&str>| {
+            world
+                .active_interactions()
+                .map(|(_, _, interaction, duration)| (interaction.is_overlap(), duration))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(durations(&world), vec![(false, 0)]);
+        for _ in 0..5 {
+            world.step(dt());
+        }
+        assert_eq!(durations(&world), vec![(false, 5)]);
+
+        // a new kind of interaction starts over
+        world
+            .set_collider_state(plate_collider, ColliderState::Sensor)
+            .unwrap();
+        world.step(dt());
+        world.step(dt());
+        assert_eq!(durations(&world), vec![(true, 1)]);
+        world
+            .teleport(plate, Vec2::from(0, -16), TeleportContacts::Clear)
+            .unwrap();
+        assert_eq!(durations(&world), vec![]);
+    }
+
+    #[test]
+    fn events_come_in_canonical_order() {
+        let mut world = World::<&str>::new();
+        let mut boxes = vec![];
+        for x in [-60, -20, 20, 60] {
+            let (body, _) = add_with_collider(
+                &mut world,
+                BodyDesc::new().with_position(Vec2::from(x, -16)),
+                ColliderDesc::new(square(), "box"),
+            );
+            boxes.push(body);
+        }
+        // inserted last, so every box found it before it was in the graph
+        let (floor, _) = add_static(&mut world, Vec2::zero(), floor("floor"));
+        world.step(dt());
+        world.step(dt());
+        assert_eq!(world.active_interactions().count(), 4);
+
+        world.remove_body(boxes[2]).unwrap();
+        for body in [boxes[0], boxes[3], boxes[1]] {
+            world.body_mut(body).unwrap().velocity = Vec2::from(0, -600);
+        }
+        world.step(dt());
+        let pairs: Vec<_> = world.events().iter().map(ContactEvent::pair).collect();
+        assert_eq!(pairs.len(), 4);
+        assert!(pairs.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(world.events().iter().all(|event| matches!(
+            event,
+            ContactEvent::CollisionEnded(h1, h2, "box", "floor") if h1 < h2
+        )));
+        let body_pairs: Vec<_> = world
+            .body_events()
+            .iter()
+            .map(|event| match event {
+                BodyContactEvent::Started(b1, b2, _) | BodyContactEvent::Ended(b1, b2, _) => {
+                    (*b1, *b2)
+                }
+            })
+            .collect();
+        assert_eq!(
+            body_pairs,
+            boxes.iter().map(|body| (*body, floor)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn colliders_opt_out_of_events() {
+        let mut world = World::<&str>::new();
+        let (ground, tile) = add_static(
+            &mut world,
+            Vec2::zero(),
+            floor("tile").with_events(ColliderEvents::None),
+        );
+        world
+            .add_collider(
+                ColliderDesc::new(square(), "coin")
+                    .with_offset(Vec2::from(0, -16))
+                    .sensor()
+                    .with_events(ColliderEvents::Overlaps),
+                ground,
+            )
+            .unwrap();
+        add_with_collider(
+            &mut world,
+            BodyDesc::new()
+                .with_position(Vec2::from(0, -40))
+                .with_velocity(Vec2::from(0, 600)),
+            ColliderDesc::new(square(), "player"),
+        );
+
+        let mut events = vec![];
+        for _ in 0..4 {
+            world.step(dt());
+            events.extend_from_slice(world.events());
+        }
+        assert!(matches!(
+            events.as_slice(),
+            [ContactEvent::OverlapStarted(_, _, "player", "coin")]
+        ));
+        // still tracked without the events
+        assert_eq!(world.physics.collisions_of(tile).count(), 1);
+
+        world.remove_collider(tile).unwrap();
+        world.step(dt());
+        assert!(world.events().is_empty());
+    }
+
+    fn land_on_floor<N: Scalar>() -> Vec2<N> {
+        let vec = |x, y| Vec2::new(N::from_i32(x), N::from_i32(y));
+        let mut world = PhysicsWorld::<(), N>::new();
+        let mut bodies = BodySet::new();
+        let mut colliders = ColliderSet::new();
+        let ground = bodies.insert(
+            BodyDesc::new()
+                .with_position(vec(32, 40))
+                .make_static()
+                .build(),
+        );
+        colliders.insert(
+            ColliderDesc::new(
+                AABB {
+                    half_exts: vec(32, 8),
+                },
+                (),
+            )
+            .build(ground),
+            &mut bodies,
+            &mut world,
+        );
+
+        let body = bodies.insert(
+            BodyDesc::new()
+                .with_position(vec(32, 0))
+                .with_velocity(vec(0, 600))
+                .build(),
+        );
+        colliders.insert(
+            ColliderDesc::new(
+                AABB {
+                    half_exts: vec(8, 8),
+                },
+                (),
+            )
+            .build(body),
+            &mut bodies,
+            &mut world,
+        );
+        for _ in 0..10 {
+            world.step(N::from_f64(1. / 60.), &mut bodies, &mut colliders);
+        }
+        bodies[body].position
+    }
+
+    #[test]
+    fn steps_with_other_scalars() {
+        let position = land_on_floor::<FP>();
+        assert_eq!((position.x(), position.y()), (to_fp(32), to_fp(24)));
+        let position = land_on_floor::<fixed::types::I32F32>();
+        assert_eq!(position.y(), fixed::types::I32F32::from_num(24));
+        let position = land_on_floor::<f32>();
+        assert!((position.y() - 24.).abs() < 0.01);
+    }
 }